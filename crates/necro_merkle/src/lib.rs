@@ -0,0 +1,134 @@
+// Snapshot merkle trees for necro_migrate.
+//
+// The program verifies proofs with this crate and off-chain tools build trees
+// and proofs with it, so both sides hash leaves and nodes the same way.
+// Internal nodes hash the sorted pair of children, so proofs don't carry
+// left/right flags. With an odd number of nodes the last one is carried up a
+// level unchanged, so proofs can be shorter than the tree height.
+//
+// `HashVersion::Legacy` hashes leaves as `H(fields)` and nodes as `H(a, b)`.
+// `HashVersion::V1` hashes leaves as `H(0x00, program_id, migration, fields)`
+// and nodes as `H(0x01, a, b)`, so a node can't pass as a leaf and a root only
+// verifies for the migration it was built for.
+//
+// The crate is `no_std`. Tree building needs the `alloc` feature, and the
+// `sha2` feature provides a portable SHA-256; on-chain code plugs in the
+// syscall through `HashFn`.
+
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+mod tree;
+
+#[cfg(feature = "alloc")]
+pub use tree::MerkleTree;
+
+use core::marker::PhantomData;
+
+/// Prefix of every V1 leaf preimage
+pub const LEAF_PREFIX: u8 = 0x00;
+/// Prefix of every V1 internal node preimage
+pub const NODE_PREFIX: u8 = 0x01;
+/// Fields in the largest leaf (vesting)
+const MAX_LEAF_FIELDS: usize = 4;
+
+/// SHA-256 over the concatenation of `parts`
+pub trait HashFn {
+    fn hashv(parts: &[&[u8]]) -> [u8; 32];
+}
+
+#[cfg(feature = "sha2")]
+pub struct Sha256;
+
+#[cfg(feature = "sha2")]
+impl HashFn for Sha256 {
+    fn hashv(parts: &[&[u8]]) -> [u8; 32] {
+        use sha2::Digest;
+        let mut hasher = sha2::Sha256::new();
+        for part in parts {
+            hasher.update(part);
+        }
+        hasher.finalize().into()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HashVersion {
+    Legacy,
+    V1,
+}
+
+/// Leaf and node hashing for one migration's snapshots
+pub struct SnapshotHasher<H> {
+    pub version: HashVersion,
+    pub program_id: [u8; 32],
+    pub migration: [u8; 32],
+    hash: PhantomData<H>,
+}
+
+impl<H> Clone for SnapshotHasher<H> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<H> Copy for SnapshotHasher<H> {}
+
+impl<H: HashFn> SnapshotHasher<H> {
+    pub fn new(version: HashVersion, program_id: [u8; 32], migration: [u8; 32]) -> Self {
+        Self { version, program_id, migration, hash: PhantomData }
+    }
+
+    /// Hash a leaf made of `fields`, adding the V1 domain if needed
+    pub fn leaf(&self, fields: &[&[u8]]) -> [u8; 32] {
+        match self.version {
+            HashVersion::Legacy => H::hashv(fields),
+            HashVersion::V1 => {
+                let mut preimage: [&[u8]; 3 + MAX_LEAF_FIELDS] = [&[]; 3 + MAX_LEAF_FIELDS];
+                assert!(fields.len() <= MAX_LEAF_FIELDS, "too many leaf fields");
+                preimage[0] = &[LEAF_PREFIX];
+                preimage[1] = &self.program_id;
+                preimage[2] = &self.migration;
+                preimage[3..3 + fields.len()].copy_from_slice(fields);
+                H::hashv(&preimage[..3 + fields.len()])
+            }
+        }
+    }
+
+    /// Leaf committed in the snapshot for a Solana wallet
+    pub fn wallet_leaf(&self, wallet: &[u8; 32], amount: u64, leaf_index: u32) -> [u8; 32] {
+        self.leaf(&[wallet, &amount.to_le_bytes(), &leaf_index.to_le_bytes()])
+    }
+
+    /// Leaf committed in the snapshot for an EVM holder
+    pub fn evm_leaf(&self, evm_address: &[u8; 20], amount: u64, leaf_index: u32) -> [u8; 32] {
+        self.leaf(&[evm_address, &amount.to_le_bytes(), &leaf_index.to_le_bytes()])
+    }
+
+    /// Leaf for a vesting allocation; `schedule` is start, cliff and duration
+    /// as little-endian i64s
+    pub fn vesting_leaf(&self, wallet: &[u8; 32], amount: u64, leaf_index: u32, schedule: &[u8; 24]) -> [u8; 32] {
+        self.leaf(&[wallet, &amount.to_le_bytes(), &leaf_index.to_le_bytes(), schedule])
+    }
+
+    /// Parent of two nodes, in either order
+    pub fn node(&self, a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        let (left, right) = if a < b { (a, b) } else { (b, a) };
+        match self.version {
+            HashVersion::Legacy => H::hashv(&[left, right]),
+            HashVersion::V1 => H::hashv(&[&[NODE_PREFIX], left, right]),
+        }
+    }
+
+    /// Fold a proof from `leaf` up to the root
+    pub fn compute_root(&self, leaf: [u8; 32], proof: &[[u8; 32]]) -> [u8; 32] {
+        proof.iter().fold(leaf, |current, sibling| self.node(&current, sibling))
+    }
+
+    pub fn verify(&self, leaf: [u8; 32], proof: &[[u8; 32]], root: &[u8; 32]) -> bool {
+        self.compute_root(leaf, proof) == *root
+    }
+}
@@ -0,0 +1,55 @@
+use alloc::vec::Vec;
+
+use crate::{HashFn, SnapshotHasher};
+
+/// Every level of a snapshot tree, leaves first
+pub struct MerkleTree {
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    /// Build a tree over `leaves` in leaf-index order. Returns None when empty.
+    pub fn new<H: HashFn>(hasher: &SnapshotHasher<H>, leaves: Vec<[u8; 32]>) -> Option<Self> {
+        if leaves.is_empty() {
+            return None;
+        }
+        let mut levels = alloc::vec![leaves];
+        while levels[levels.len() - 1].len() > 1 {
+            let next = levels[levels.len() - 1]
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => hasher.node(a, b),
+                    [last] => *last,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+        Some(Self { levels })
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.levels[self.levels.len() - 1][0]
+    }
+
+    pub fn leaves(&self) -> &[[u8; 32]] {
+        &self.levels[0]
+    }
+
+    /// Siblings from the leaf up, or None if `leaf_index` is out of range
+    pub fn proof(&self, leaf_index: usize) -> Option<Vec<[u8; 32]>> {
+        if leaf_index >= self.leaves().len() {
+            return None;
+        }
+        let mut proof = Vec::new();
+        let mut index = leaf_index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            // A carried-up last node has no sibling at this level
+            if let Some(sibling) = level.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+        Some(proof)
+    }
+}
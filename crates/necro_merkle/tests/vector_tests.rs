@@ -0,0 +1,120 @@
+use necro_merkle::{HashVersion, MerkleTree, Sha256, SnapshotHasher};
+use serde::Deserialize;
+
+const VECTORS: &str = include_str!("../vectors/snapshot_vectors.json");
+
+#[derive(Deserialize)]
+struct Vectors {
+    cases: Vec<Case>,
+}
+
+#[derive(Deserialize)]
+struct Case {
+    name: String,
+    hash_version: String,
+    program_id: String,
+    migration: String,
+    root: String,
+    leaves: Vec<Leaf>,
+}
+
+#[derive(Deserialize)]
+struct Leaf {
+    kind: String,
+    wallet: Option<String>,
+    evm_address: Option<String>,
+    schedule: Option<Schedule>,
+    amount: String,
+    leaf_index: u32,
+    leaf: String,
+    proof: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct Schedule {
+    start_ts: i64,
+    cliff_ts: i64,
+    duration: i64,
+}
+
+fn bytes<const N: usize>(hex_str: &str) -> [u8; N] {
+    hex::decode(hex_str).unwrap().try_into().unwrap()
+}
+
+fn hasher(case: &Case) -> SnapshotHasher<Sha256> {
+    let version = match case.hash_version.as_str() {
+        "legacy" => HashVersion::Legacy,
+        "v1" => HashVersion::V1,
+        other => panic!("unknown hash_version {other}"),
+    };
+    SnapshotHasher::new(version, bytes(&case.program_id), bytes(&case.migration))
+}
+
+fn leaf_hash(hasher: &SnapshotHasher<Sha256>, leaf: &Leaf) -> [u8; 32] {
+    let amount = leaf.amount.parse().unwrap();
+    match leaf.kind.as_str() {
+        "wallet" => hasher.wallet_leaf(&bytes(leaf.wallet.as_ref().unwrap()), amount, leaf.leaf_index),
+        "evm" => hasher.evm_leaf(&bytes(leaf.evm_address.as_ref().unwrap()), amount, leaf.leaf_index),
+        "vesting" => {
+            let schedule = leaf.schedule.as_ref().unwrap();
+            let mut schedule_bytes = [0u8; 24];
+            schedule_bytes[..8].copy_from_slice(&schedule.start_ts.to_le_bytes());
+            schedule_bytes[8..16].copy_from_slice(&schedule.cliff_ts.to_le_bytes());
+            schedule_bytes[16..].copy_from_slice(&schedule.duration.to_le_bytes());
+            hasher.vesting_leaf(&bytes(leaf.wallet.as_ref().unwrap()), amount, leaf.leaf_index, &schedule_bytes)
+        }
+        other => panic!("unknown leaf kind {other}"),
+    }
+}
+
+#[test]
+fn test_vectors_match_tree_builder() {
+    let vectors: Vectors = serde_json::from_str(VECTORS).unwrap();
+    assert!(!vectors.cases.is_empty());
+
+    for case in &vectors.cases {
+        let hasher = hasher(case);
+        let leaves: Vec<[u8; 32]> = case.leaves.iter().map(|leaf| leaf_hash(&hasher, leaf)).collect();
+        for (leaf, expected) in leaves.iter().zip(&case.leaves) {
+            assert_eq!(hex::encode(leaf), expected.leaf, "{}: leaf {}", case.name, expected.leaf_index);
+        }
+
+        let tree = MerkleTree::new(&hasher, leaves.clone()).unwrap();
+        assert_eq!(hex::encode(tree.root()), case.root, "{}: root", case.name);
+
+        for (index, expected) in case.leaves.iter().enumerate() {
+            let proof = tree.proof(index).unwrap();
+            let proof_hex: Vec<String> = proof.iter().map(hex::encode).collect();
+            assert_eq!(proof_hex, expected.proof, "{}: proof {}", case.name, index);
+            assert!(hasher.verify(leaves[index], &proof, &tree.root()));
+        }
+        println!("✓ {}", case.name);
+    }
+}
+
+#[test]
+fn test_v1_roots_do_not_verify_for_other_migrations() {
+    let vectors: Vectors = serde_json::from_str(VECTORS).unwrap();
+    let case = vectors.cases.iter().find(|case| case.hash_version == "v1" && case.leaves.len() > 1).unwrap();
+    let other = SnapshotHasher::<Sha256>::new(HashVersion::V1, bytes(&case.program_id), [7u8; 32]);
+
+    let leaf = &case.leaves[0];
+    let proof: Vec<[u8; 32]> = leaf.proof.iter().map(|node| bytes(node)).collect();
+    assert!(!other.verify(leaf_hash(&other, leaf), &proof, &bytes(&case.root)));
+    println!("✓ V1 roots are bound to their migration");
+}
+
+#[test]
+fn test_tree_edge_cases() {
+    let hasher = SnapshotHasher::<Sha256>::new(HashVersion::V1, [1u8; 32], [2u8; 32]);
+    assert!(MerkleTree::new(&hasher, Vec::new()).is_none());
+
+    let leaves: Vec<[u8; 32]> = (0..3u32).map(|i| hasher.wallet_leaf(&[9u8; 32], 1, i)).collect();
+    let tree = MerkleTree::new(&hasher, leaves.clone()).unwrap();
+    assert!(tree.proof(3).is_none());
+
+    // The unpaired third leaf is carried up, so its proof skips a level
+    assert_eq!(tree.proof(2).unwrap(), vec![hasher.node(&leaves[0], &leaves[1])]);
+    assert_eq!(tree.proof(0).unwrap().len(), 2);
+    println!("✓ Empty trees and unpaired leaves");
+}
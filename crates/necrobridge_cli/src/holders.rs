@@ -0,0 +1,131 @@
+// Holder lists fed into `snapshot build`.
+//
+// CSV files need `address` and `amount` columns; JSON files are an array of
+// `{ "address": ..., "amount": ... }` objects, with amounts as numbers or
+// decimal strings. Rows keep their order and become the leaf indices.
+// Addresses are Solana base58 pubkeys or 0x-prefixed EVM addresses, which
+// claim through `claim_tokens` and `claim_tokens_evm` respectively.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Deserialize;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Address {
+    Solana([u8; 32]),
+    Evm([u8; 20]),
+}
+
+impl FromStr for Address {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if let Some(hex_str) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            let bytes = hex::decode(hex_str).with_context(|| format!("invalid EVM address {s}"))?;
+            let address = bytes.try_into().map_err(|_| anyhow!("EVM address {s} is not 20 bytes"))?;
+            return Ok(Address::Evm(address));
+        }
+        let bytes = bs58::decode(s).into_vec().with_context(|| format!("invalid Solana address {s}"))?;
+        let address = bytes.try_into().map_err(|_| anyhow!("Solana address {s} is not 32 bytes"))?;
+        Ok(Address::Solana(address))
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Address::Solana(bytes) => write!(f, "{}", bs58::encode(bytes).into_string()),
+            // Lowercase so lookups don't depend on EIP-55 checksum casing
+            Address::Evm(bytes) => write!(f, "0x{}", hex::encode(bytes)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Holder {
+    pub address: Address,
+    pub amount: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawAmount {
+    Number(u64),
+    Text(String),
+}
+
+#[derive(Deserialize)]
+struct RawHolder {
+    address: String,
+    amount: RawAmount,
+}
+
+impl RawHolder {
+    fn parse(self, row: usize) -> Result<Holder> {
+        let address = self.address.parse().with_context(|| format!("row {row}"))?;
+        let amount = match self.amount {
+            RawAmount::Number(amount) => amount,
+            RawAmount::Text(text) => text
+                .trim()
+                .parse()
+                .with_context(|| format!("row {row}: invalid amount {text:?}"))?,
+        };
+        Ok(Holder { address, amount })
+    }
+}
+
+/// Read holders from a `.csv` or `.json` file
+pub fn read_holders(path: &Path) -> Result<Vec<Holder>> {
+    let contents = std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("csv") => parse_csv(&contents),
+        Some("json") => parse_json(&contents),
+        _ => bail!("{}: expected a .csv or .json holder list", path.display()),
+    }
+}
+
+pub fn parse_csv(contents: &str) -> Result<Vec<Holder>> {
+    let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(contents.as_bytes());
+    let mut holders = Vec::new();
+    for (row, record) in reader.deserialize::<RawHolder>().enumerate() {
+        holders.push(record.with_context(|| format!("row {row}"))?.parse(row)?);
+    }
+    check_holders(&holders)?;
+    Ok(holders)
+}
+
+pub fn parse_json(contents: &str) -> Result<Vec<Holder>> {
+    let raw: Vec<RawHolder> = serde_json::from_str(contents).context("parsing holder list")?;
+    let holders = raw
+        .into_iter()
+        .enumerate()
+        .map(|(row, holder)| holder.parse(row))
+        .collect::<Result<Vec<_>>>()?;
+    check_holders(&holders)?;
+    Ok(holders)
+}
+
+/// Claims need a positive amount, and each address can only claim once
+fn check_holders(holders: &[Holder]) -> Result<()> {
+    if holders.is_empty() {
+        bail!("holder list is empty");
+    }
+    if holders.len() > u32::MAX as usize {
+        bail!("too many holders for u32 leaf indices");
+    }
+    let mut seen = HashSet::new();
+    for (row, holder) in holders.iter().enumerate() {
+        if holder.amount == 0 {
+            bail!("row {row}: {} has a zero amount", holder.address);
+        }
+        if !seen.insert(holder.address) {
+            bail!("row {row}: duplicate address {}", holder.address);
+        }
+    }
+    Ok(())
+}
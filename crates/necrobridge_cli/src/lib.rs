@@ -0,0 +1,7 @@
+// Library side of the `necrobridge` CLI, so snapshot building can be reused
+// and tested without going through the binary.
+
+pub mod holders;
+pub mod manifest;
+pub mod replay;
+pub mod snapshot;
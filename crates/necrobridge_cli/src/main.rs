@@ -0,0 +1,255 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use clap::{Args, Parser, Subcommand};
+use necrobridge_cli::holders::{read_holders, Holder};
+use necrobridge_cli::manifest::{apply_exclusions, Manifest, SnapshotRules};
+use necrobridge_cli::replay::{self, ReplayOptions};
+use necrobridge_cli::snapshot::{self, parse_pubkey, HashParams, HashVersion, Snapshot, NECRO_MIGRATE_PROGRAM_ID};
+
+#[derive(Parser)]
+#[command(name = "necrobridge", about = "Build and check necro_migrate snapshots")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Snapshot roots and proofs
+    #[command(subcommand)]
+    Snapshot(SnapshotCommand),
+    /// Look up proofs in a snapshot file
+    #[command(subcommand)]
+    Proof(ProofCommand),
+    /// Snapshot manifests committed on-chain as `manifest_hash`
+    #[command(subcommand)]
+    Manifest(ManifestCommand),
+    /// Re-check every proof in a snapshot file
+    Verify {
+        #[arg(long, default_value = "snapshot.json")]
+        snapshot: PathBuf,
+        /// Also rebuild the tree from these holders and compare roots
+        #[command(flatten)]
+        source: HolderSource,
+    },
+}
+
+/// Where holders come from: a holder list, or Transfer logs replayed to a block
+#[derive(Args)]
+struct HolderSource {
+    /// CSV or JSON holder list
+    #[arg(long, conflicts_with = "transfers")]
+    holders: Option<PathBuf>,
+    /// Exported Transfer logs (JSONL or JSON array) to replay
+    #[arg(long, requires = "block")]
+    transfers: Option<PathBuf>,
+    /// Last block included in the replay
+    #[arg(long)]
+    block: Option<u64>,
+    /// Only replay logs emitted by this token contract
+    #[arg(long)]
+    token: Option<String>,
+    /// Address left out of the snapshot (burn address, contract, source LP)
+    #[arg(long)]
+    exclude: Vec<String>,
+    /// File of addresses to exclude, one per line
+    #[arg(long)]
+    exclude_file: Option<PathBuf>,
+    /// Divide replayed balances by 10^N, e.g. 9 to snapshot an 18-decimal
+    /// token at 9 decimals
+    #[arg(long, default_value_t = 0)]
+    scale_down: u8,
+}
+
+impl HolderSource {
+    fn exclusions(&self) -> Result<HashSet<[u8; 20]>> {
+        let mut exclude = replay::parse_exclusions(&self.exclude.join("\n"))?;
+        if let Some(path) = &self.exclude_file {
+            let contents = std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+            exclude.extend(replay::parse_exclusions(&contents)?);
+        }
+        Ok(exclude)
+    }
+
+    /// Excluded addresses are dropped from holder lists too
+    fn load(&self) -> Result<Option<Vec<Holder>>> {
+        if let Some(holders) = &self.holders {
+            return Ok(Some(apply_exclusions(&read_holders(holders)?, &self.exclusions()?)));
+        }
+        let Some(transfers) = &self.transfers else {
+            return Ok(None);
+        };
+
+        let options = ReplayOptions {
+            block: self.block.expect("clap requires --block with --transfers"),
+            token: self.token.as_deref().map(replay::parse_evm).transpose()?,
+            exclude: self.exclusions()?,
+        };
+
+        let transfers = replay::read_transfers(transfers)?;
+        let balances = replay::replay(&transfers, &options)?;
+        let report = replay::to_holders(&balances, &options.exclude, self.scale_down)?;
+        println!(
+            "replayed {} transfers up to block {}: {} holders, {} excluded, {} dust, {} below one unit",
+            transfers.len(),
+            options.block,
+            report.holders.len(),
+            report.excluded,
+            report.dust,
+            report.dropped
+        );
+        Ok(Some(report.holders))
+    }
+}
+
+#[derive(Subcommand)]
+enum SnapshotCommand {
+    /// Build a snapshot from a holder list or replayed Transfer logs
+    Build {
+        #[command(flatten)]
+        source: HolderSource,
+        #[arg(long, value_enum, default_value = "v1")]
+        hash_version: HashVersion,
+        /// Migration PDA the V1 leaves are bound to
+        #[arg(long)]
+        migration: Option<String>,
+        #[arg(long, default_value = NECRO_MIGRATE_PROGRAM_ID)]
+        program_id: String,
+        #[arg(long, default_value = "snapshot.json")]
+        out: PathBuf,
+        /// Also write a manifest; needs --source-chain, --token and --block
+        #[arg(long, requires_all = ["source_chain", "token", "block"])]
+        manifest: Option<PathBuf>,
+        /// Wormhole chain id of the source chain
+        #[arg(long)]
+        source_chain: Option<u16>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ManifestCommand {
+    /// Print the manifest hash to pass to initialize_migration
+    Hash {
+        #[arg(long, default_value = "manifest.json")]
+        manifest: PathBuf,
+    },
+    /// Rebuild the snapshot from raw inputs with the manifest's rules and
+    /// check the root, counts and hash
+    Verify {
+        #[arg(long, default_value = "manifest.json")]
+        manifest: PathBuf,
+        /// Holder list the snapshot was built from
+        #[arg(long, conflicts_with = "transfers")]
+        holders: Option<PathBuf>,
+        /// Transfer logs the snapshot was replayed from
+        #[arg(long, required_unless_present = "holders")]
+        transfers: Option<PathBuf>,
+        /// Expected hash, e.g. `manifest_hash` read from the Migration account
+        #[arg(long)]
+        expect_hash: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ProofCommand {
+    /// Print one holder's claim and proof as JSON
+    Get {
+        address: String,
+        #[arg(long, default_value = "snapshot.json")]
+        snapshot: PathBuf,
+    },
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T> {
+    let contents = std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    serde_json::from_str(&contents).with_context(|| format!("parsing {}", path.display()))
+}
+
+fn read_snapshot(path: &Path) -> Result<Snapshot> {
+    read_json(path)
+}
+
+fn main() -> Result<()> {
+    match Cli::parse().command {
+        Command::Snapshot(SnapshotCommand::Build {
+            source,
+            hash_version,
+            migration,
+            program_id,
+            out,
+            manifest,
+            source_chain,
+        }) => {
+            let Some(holders) = source.load()? else {
+                bail!("pass --holders or --transfers");
+            };
+            let migration = match (hash_version, migration) {
+                (_, Some(migration)) => parse_pubkey(&migration)?,
+                (HashVersion::Legacy, None) => [0u8; 32],
+                (HashVersion::V1, None) => bail!("--migration is required for v1 snapshots"),
+            };
+            let params = HashParams { hash_version, program_id: parse_pubkey(&program_id)?, migration };
+            let snapshot = snapshot::build(&holders, &params)?;
+            std::fs::write(&out, serde_json::to_string_pretty(&snapshot)?)
+                .with_context(|| format!("writing {}", out.display()))?;
+
+            println!("root: {}", snapshot.root);
+            println!("holders: {}", snapshot.claims.len());
+            println!("total amount: {}", snapshot.total_amount);
+            println!("proofs written to {}", out.display());
+
+            if let Some(path) = manifest {
+                let rules = SnapshotRules {
+                    source_chain: source_chain.expect("clap requires --source-chain with --manifest"),
+                    source_token: replay::parse_evm(source.token.as_deref().expect("clap requires --token"))?,
+                    block_height: source.block.expect("clap requires --block"),
+                    exclusions: source.exclusions()?,
+                    scale_down: if source.transfers.is_some() { source.scale_down } else { 0 },
+                };
+                let manifest = Manifest::new(&rules, &snapshot);
+                std::fs::write(&path, serde_json::to_string_pretty(&manifest)?)
+                    .with_context(|| format!("writing {}", path.display()))?;
+                println!("manifest hash: {}", hex::encode(manifest.hash()?));
+                println!("manifest written to {}", path.display());
+            }
+        }
+        Command::Proof(ProofCommand::Get { address, snapshot }) => {
+            let snapshot = read_snapshot(&snapshot)?;
+            println!("{}", serde_json::to_string_pretty(snapshot.claim(&address)?)?);
+        }
+        Command::Manifest(ManifestCommand::Hash { manifest }) => {
+            let manifest: Manifest = read_json(&manifest)?;
+            println!("{}", hex::encode(manifest.hash()?));
+        }
+        Command::Manifest(ManifestCommand::Verify { manifest, holders, transfers, expect_hash }) => {
+            let manifest: Manifest = read_json(&manifest)?;
+            let snapshot = match (holders, transfers) {
+                (Some(holders), _) => manifest.verify_holders(&read_holders(&holders)?)?,
+                (None, Some(transfers)) => manifest.verify_transfers(&replay::read_transfers(&transfers)?)?,
+                (None, None) => unreachable!("clap requires --holders or --transfers"),
+            };
+            let hash = hex::encode(manifest.hash()?);
+            if let Some(expected) = expect_hash {
+                if !expected.trim_start_matches("0x").eq_ignore_ascii_case(&hash) {
+                    bail!("manifest hash {hash} does not match expected {expected}");
+                }
+            }
+            println!("✓ {} leaves rebuild root {}", snapshot.claims.len(), snapshot.root);
+            println!("manifest hash: {hash}");
+        }
+        Command::Verify { snapshot: path, source } => {
+            let snapshot = read_snapshot(&path)?;
+            snapshot.verify()?;
+            if let Some(holders) = source.load()? {
+                let rebuilt = snapshot::build(&holders, &snapshot.params()?)?;
+                if rebuilt.root != snapshot.root {
+                    bail!("holders build root {}, snapshot has {}", rebuilt.root, snapshot.root);
+                }
+            }
+            println!("✓ {} proofs verify against {}", snapshot.claims.len(), snapshot.root);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,170 @@
+// Snapshot manifests: everything needed to reproduce a snapshot root from raw
+// inputs. The manifest hash is passed to `initialize_migration` (and to
+// SetSnapshotRoot operations) and stored as `Migration.manifest_hash`.
+//
+// The hash is sha256 over the canonical encoding: the manifest as compact JSON
+// with fields in the order declared below, no whitespace, addresses as
+// lowercase 0x-hex, exclusions sorted and deduplicated, and amounts as decimal
+// strings. Any implementation that emits the same bytes gets the same hash;
+// the pretty-printed file on disk is not what gets hashed.
+
+use std::collections::{BTreeSet, HashSet};
+
+use anyhow::{bail, Context, Result};
+use necro_merkle::{HashFn, Sha256};
+use serde::{Deserialize, Serialize};
+
+use crate::holders::{Address, Holder};
+use crate::replay::{self, parse_evm, ReplayOptions, Transfer};
+use crate::snapshot::{self, HashParams, HashVersion, Snapshot};
+
+pub const MANIFEST_VERSION: u8 = 1;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub version: u8,
+    /// Wormhole chain id of the source chain, as in `Migration.source_chain`
+    pub source_chain: u16,
+    pub source_token: String,
+    /// Last source block included in the snapshot
+    pub block_height: u64,
+    /// Addresses left out of the snapshot
+    pub exclusions: Vec<String>,
+    /// Token balances were divided by 10^scale_down (0 for holder lists)
+    pub scale_down: u8,
+    pub leaf_count: u32,
+    pub total_amount: String,
+    pub hash_version: HashVersion,
+    pub program_id: String,
+    pub migration: String,
+    pub root: String,
+}
+
+/// How holders were selected; recorded in the manifest next to the result
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SnapshotRules {
+    pub source_chain: u16,
+    pub source_token: [u8; 20],
+    pub block_height: u64,
+    pub exclusions: HashSet<[u8; 20]>,
+    pub scale_down: u8,
+}
+
+fn evm_hex(address: &[u8; 20]) -> String {
+    Address::Evm(*address).to_string()
+}
+
+impl Manifest {
+    pub fn new(rules: &SnapshotRules, snapshot: &Snapshot) -> Self {
+        let exclusions: BTreeSet<[u8; 20]> = rules.exclusions.iter().copied().collect();
+        Self {
+            version: MANIFEST_VERSION,
+            source_chain: rules.source_chain,
+            source_token: evm_hex(&rules.source_token),
+            block_height: rules.block_height,
+            exclusions: exclusions.iter().map(evm_hex).collect(),
+            scale_down: rules.scale_down,
+            leaf_count: snapshot.claims.len() as u32,
+            total_amount: snapshot.total_amount.clone(),
+            hash_version: snapshot.hash_version,
+            program_id: snapshot.program_id.clone(),
+            migration: snapshot.migration.clone(),
+            root: snapshot.root.clone(),
+        }
+    }
+
+    pub fn rules(&self) -> Result<SnapshotRules> {
+        if self.version != MANIFEST_VERSION {
+            bail!("unsupported manifest version {}", self.version);
+        }
+        Ok(SnapshotRules {
+            source_chain: self.source_chain,
+            source_token: parse_evm(&self.source_token).context("source_token")?,
+            block_height: self.block_height,
+            exclusions: self.exclusions.iter().map(|address| parse_evm(address)).collect::<Result<_>>()?,
+            scale_down: self.scale_down,
+        })
+    }
+
+    /// Canonical form of this manifest; fails if fields don't parse
+    pub fn canonical(&self) -> Result<Self> {
+        let rules = self.rules()?;
+        let mut canonical = self.clone();
+        canonical.source_token = evm_hex(&rules.source_token);
+        canonical.exclusions = rules.exclusions.iter().collect::<BTreeSet<_>>().into_iter().map(evm_hex).collect();
+        canonical.root = canonical.root.to_lowercase();
+        Ok(canonical)
+    }
+
+    pub fn canonical_bytes(&self) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(&self.canonical()?)?)
+    }
+
+    /// Value for `Migration.manifest_hash`
+    pub fn hash(&self) -> Result<[u8; 32]> {
+        Ok(Sha256::hashv(&[&self.canonical_bytes()?]))
+    }
+
+    fn hash_params(&self) -> Result<HashParams> {
+        Ok(HashParams {
+            hash_version: self.hash_version,
+            program_id: snapshot::parse_pubkey(&self.program_id)?,
+            migration: snapshot::parse_pubkey(&self.migration)?,
+        })
+    }
+
+    /// Check a rebuilt snapshot against what the manifest claims
+    pub fn check(&self, snapshot: &Snapshot) -> Result<()> {
+        let expected = Manifest::new(&self.rules()?, snapshot);
+        if expected.canonical()? != self.canonical()? {
+            bail!(
+                "manifest does not match the rebuilt snapshot: root {} ({} leaves, {} total), manifest has {} ({} leaves, {} total)",
+                snapshot.root,
+                expected.leaf_count,
+                expected.total_amount,
+                self.root,
+                self.leaf_count,
+                self.total_amount
+            );
+        }
+        Ok(())
+    }
+
+    /// Replay `transfers` with the manifest's rules, rebuild the tree and
+    /// check it matches
+    pub fn verify_transfers(&self, transfers: &[Transfer]) -> Result<Snapshot> {
+        let rules = self.rules()?;
+        let options = ReplayOptions {
+            block: rules.block_height,
+            token: Some(rules.source_token),
+            exclude: rules.exclusions.clone(),
+        };
+        let balances = replay::replay(transfers, &options)?;
+        let report = replay::to_holders(&balances, &rules.exclusions, rules.scale_down)?;
+        let snapshot = snapshot::build(&report.holders, &self.hash_params()?)?;
+        self.check(&snapshot)?;
+        Ok(snapshot)
+    }
+
+    /// Rebuild from a holder list, dropping excluded addresses, and check it
+    /// matches
+    pub fn verify_holders(&self, holders: &[Holder]) -> Result<Snapshot> {
+        let rules = self.rules()?;
+        if rules.scale_down != 0 {
+            bail!("manifest scales replayed balances; verify it against the transfer logs");
+        }
+        let holders = apply_exclusions(holders, &rules.exclusions);
+        let snapshot = snapshot::build(&holders, &self.hash_params()?)?;
+        self.check(&snapshot)?;
+        Ok(snapshot)
+    }
+}
+
+/// Drop holders whose EVM address is excluded
+pub fn apply_exclusions(holders: &[Holder], exclusions: &HashSet<[u8; 20]>) -> Vec<Holder> {
+    holders
+        .iter()
+        .filter(|holder| !matches!(holder.address, Address::Evm(address) if exclusions.contains(&address)))
+        .cloned()
+        .collect()
+}
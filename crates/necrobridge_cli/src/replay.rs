@@ -0,0 +1,266 @@
+// Replays exported ERC-20 `Transfer` logs into balances at a block height.
+//
+// Input is JSONL or a JSON array, one entry per log, in either shape:
+// - Raw logs as returned by `eth_getLogs` or found in hardhat/ethers receipts
+//   (`receipt.logs`): `address`, `topics`, `data`, `blockNumber`, `logIndex`.
+//   Logs whose first topic isn't `Transfer(address,address,uint256)` are
+//   skipped, as are `removed` ones.
+// - Decoded transfers: `block_number`, `log_index`, `from`, `to`, `value`.
+//
+// Logs are applied in (block, log index) order up to and including the
+// snapshot block. Transfers from the zero address mint and transfers to it
+// burn; a transfer that spends more than the sender holds means the export is
+// incomplete and fails the replay. Excluded addresses (burn addresses,
+// contracts, the source LP) keep their balances during the replay but are
+// left out of the holder list.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Deserialize;
+
+use crate::holders::{Address, Holder};
+
+/// keccak256("Transfer(address,address,uint256)")
+pub const TRANSFER_TOPIC: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Transfer {
+    pub block_number: u64,
+    pub log_index: u64,
+    pub token: Option<[u8; 20]>,
+    pub from: [u8; 20],
+    pub to: [u8; 20],
+    pub value: u128,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Quantity {
+    Number(u64),
+    Text(String),
+}
+
+impl Quantity {
+    fn parse(&self) -> Result<u64> {
+        match self {
+            Quantity::Number(n) => Ok(*n),
+            Quantity::Text(text) => match text.strip_prefix("0x") {
+                Some(hex_str) => u64::from_str_radix(hex_str, 16).with_context(|| format!("invalid quantity {text}")),
+                None => text.parse().with_context(|| format!("invalid quantity {text}")),
+            },
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawLog {
+    address: Option<String>,
+    topics: Vec<String>,
+    data: String,
+    block_number: Quantity,
+    log_index: Quantity,
+    #[serde(default)]
+    removed: bool,
+}
+
+#[derive(Deserialize)]
+struct DecodedTransfer {
+    block_number: Quantity,
+    log_index: Quantity,
+    token: Option<String>,
+    from: String,
+    to: String,
+    value: String,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LogEntry {
+    Raw(RawLog),
+    Decoded(DecodedTransfer),
+}
+
+pub fn parse_evm(s: &str) -> Result<[u8; 20]> {
+    match s.parse::<Address>()? {
+        Address::Evm(address) => Ok(address),
+        Address::Solana(_) => bail!("{s} is not an EVM address"),
+    }
+}
+
+fn parse_word(word: &str) -> Result<[u8; 32]> {
+    let hex_str = word.strip_prefix("0x").unwrap_or(word);
+    let bytes = hex::decode(hex_str).with_context(|| format!("invalid 32-byte word {word}"))?;
+    bytes.try_into().map_err(|_| anyhow!("{word} is not a 32-byte word"))
+}
+
+/// Address from a left-padded topic
+fn topic_address(topic: &str) -> Result<[u8; 20]> {
+    let word = parse_word(topic)?;
+    if word[..12].iter().any(|b| *b != 0) {
+        bail!("topic {topic} is not an address");
+    }
+    Ok(word[12..].try_into().unwrap())
+}
+
+/// uint256 that has to fit in a u128
+fn word_value(word: &[u8; 32]) -> Result<u128> {
+    if word[..16].iter().any(|b| *b != 0) {
+        bail!("transfer value 0x{} does not fit in u128", hex::encode(word));
+    }
+    Ok(u128::from_be_bytes(word[16..].try_into().unwrap()))
+}
+
+impl LogEntry {
+    fn into_transfer(self) -> Result<Option<Transfer>> {
+        match self {
+            LogEntry::Raw(log) => {
+                if log.removed || !log.topics.first().is_some_and(|t| t.eq_ignore_ascii_case(TRANSFER_TOPIC)) {
+                    return Ok(None);
+                }
+                // ERC-721 Transfer shares the signature but indexes the token id
+                let [_, from, to] = &log.topics[..] else {
+                    return Ok(None);
+                };
+                Ok(Some(Transfer {
+                    block_number: log.block_number.parse()?,
+                    log_index: log.log_index.parse()?,
+                    token: log.address.as_deref().map(parse_evm).transpose()?,
+                    from: topic_address(from)?,
+                    to: topic_address(to)?,
+                    value: word_value(&parse_word(&log.data)?)?,
+                }))
+            }
+            LogEntry::Decoded(transfer) => Ok(Some(Transfer {
+                block_number: transfer.block_number.parse()?,
+                log_index: transfer.log_index.parse()?,
+                token: transfer.token.as_deref().map(parse_evm).transpose()?,
+                from: parse_evm(&transfer.from)?,
+                to: parse_evm(&transfer.to)?,
+                value: transfer.value.parse().with_context(|| format!("invalid value {}", transfer.value))?,
+            })),
+        }
+    }
+}
+
+/// Parse a JSON array or JSONL export
+pub fn parse_transfers(contents: &str) -> Result<Vec<Transfer>> {
+    let entries: Vec<LogEntry> = if contents.trim_start().starts_with('[') {
+        serde_json::from_str(contents).context("parsing log array")?
+    } else {
+        contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(line, text)| serde_json::from_str(text).with_context(|| format!("line {}", line + 1)))
+            .collect::<Result<_>>()?
+    };
+    let mut transfers = Vec::new();
+    for entry in entries {
+        transfers.extend(entry.into_transfer()?);
+    }
+    Ok(transfers)
+}
+
+pub fn read_transfers(path: &Path) -> Result<Vec<Transfer>> {
+    let contents = std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    parse_transfers(&contents).with_context(|| path.display().to_string())
+}
+
+/// One address per line; blank lines and `#` comments are ignored
+pub fn parse_exclusions(contents: &str) -> Result<HashSet<[u8; 20]>> {
+    contents
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty())
+        .map(parse_evm)
+        .collect()
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ReplayOptions {
+    /// Last block included in the snapshot
+    pub block: u64,
+    /// Only replay logs emitted by this token, when logs carry an address
+    pub token: Option<[u8; 20]>,
+    pub exclude: HashSet<[u8; 20]>,
+}
+
+/// Balances of every address with a non-zero balance at `options.block`,
+/// excluded addresses included, keyed by address
+pub fn replay(transfers: &[Transfer], options: &ReplayOptions) -> Result<BTreeMap<[u8; 20], u128>> {
+    let mut ordered: Vec<&Transfer> = transfers
+        .iter()
+        .filter(|t| t.block_number <= options.block)
+        .filter(|t| options.token.is_none() || t.token.is_none() || t.token == options.token)
+        .collect();
+    ordered.sort_by_key(|t| (t.block_number, t.log_index));
+    for pair in ordered.windows(2) {
+        if (pair[0].block_number, pair[0].log_index) == (pair[1].block_number, pair[1].log_index) {
+            bail!("duplicate log {} in block {}", pair[0].log_index, pair[0].block_number);
+        }
+    }
+
+    let mut balances: BTreeMap<[u8; 20], u128> = BTreeMap::new();
+    for transfer in ordered {
+        if transfer.from != [0u8; 20] {
+            let balance = balances.entry(transfer.from).or_default();
+            *balance = balance.checked_sub(transfer.value).ok_or_else(|| {
+                anyhow!(
+                    "block {} log {}: 0x{} sends {} but holds {}; are earlier logs missing?",
+                    transfer.block_number,
+                    transfer.log_index,
+                    hex::encode(transfer.from),
+                    transfer.value,
+                    balance
+                )
+            })?;
+        }
+        if transfer.to != [0u8; 20] {
+            let balance = balances.entry(transfer.to).or_default();
+            *balance = balance.checked_add(transfer.value).ok_or_else(|| anyhow!("balance overflow"))?;
+        }
+    }
+    balances.retain(|_, balance| *balance > 0);
+    Ok(balances)
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HolderReport {
+    pub holders: Vec<Holder>,
+    /// Balance held by excluded addresses, in token units
+    pub excluded: u128,
+    /// Remainders dropped when scaling down to snapshot decimals
+    pub dust: u128,
+    /// Holders whose whole balance was below one snapshot unit
+    pub dropped: usize,
+}
+
+/// Turn balances into snapshot holders, sorted by address. Balances are in
+/// token base units and are divided by `10^scale_down` (rounding down) so
+/// they fit the u64 amounts the snapshot commits to.
+pub fn to_holders(balances: &BTreeMap<[u8; 20], u128>, exclude: &HashSet<[u8; 20]>, scale_down: u8) -> Result<HolderReport> {
+    let factor = 10u128
+        .checked_pow(scale_down as u32)
+        .ok_or_else(|| anyhow!("cannot scale down by 10^{scale_down}"))?;
+    let mut report = HolderReport::default();
+    for (address, balance) in balances {
+        if exclude.contains(address) {
+            report.excluded += balance;
+            continue;
+        }
+        report.dust += balance % factor;
+        let amount = balance / factor;
+        if amount == 0 {
+            report.dropped += 1;
+            continue;
+        }
+        let amount = u64::try_from(amount).map_err(|_| {
+            anyhow!("0x{} holds {balance}, too large for u64 after scaling; scale down further", hex::encode(address))
+        })?;
+        report.holders.push(Holder { address: Address::Evm(*address), amount });
+    }
+    Ok(report)
+}
@@ -0,0 +1,166 @@
+// Snapshot files: the root passed to `initialize_migration` plus one proof per
+// holder. Hashing goes through `necro_merkle`, the same code `claim_tokens`
+// verifies with, so a file that passes `verify` will pass on-chain as long as
+// the migration uses the same hash version (and, for V1, is the same PDA).
+
+use anyhow::{anyhow, bail, Context, Result};
+use necro_merkle::{MerkleTree, Sha256, SnapshotHasher};
+use serde::{Deserialize, Serialize};
+
+use crate::holders::{Address, Holder};
+
+/// necro_migrate's program id, bound into V1 leaves
+pub const NECRO_MIGRATE_PROGRAM_ID: &str = "2z3U1Wwq7bgHnkEuD5Yfw97g8uGyimDyRafRar21Bsva";
+
+/// Mirrors `necro_migrate::merkle::HashVersion`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum HashVersion {
+    Legacy,
+    V1,
+}
+
+impl From<HashVersion> for necro_merkle::HashVersion {
+    fn from(version: HashVersion) -> Self {
+        match version {
+            HashVersion::Legacy => necro_merkle::HashVersion::Legacy,
+            HashVersion::V1 => necro_merkle::HashVersion::V1,
+        }
+    }
+}
+
+/// What the leaves are bound to. `program_id` and `migration` only affect V1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HashParams {
+    pub hash_version: HashVersion,
+    pub program_id: [u8; 32],
+    pub migration: [u8; 32],
+}
+
+impl HashParams {
+    pub fn hasher(&self) -> SnapshotHasher<Sha256> {
+        SnapshotHasher::new(self.hash_version.into(), self.program_id, self.migration)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub hash_version: HashVersion,
+    pub program_id: String,
+    pub migration: String,
+    /// Hex; pass as `snapshot_root`
+    pub root: String,
+    /// Sum of all amounts, as a decimal string
+    pub total_amount: String,
+    pub claims: Vec<Claim>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Claim {
+    pub address: String,
+    /// Decimal string so JS readers don't lose precision
+    pub amount: String,
+    pub leaf_index: u32,
+    pub leaf: String,
+    pub proof: Vec<String>,
+}
+
+pub fn parse_pubkey(s: &str) -> Result<[u8; 32]> {
+    let bytes = bs58::decode(s).into_vec().with_context(|| format!("invalid pubkey {s}"))?;
+    bytes.try_into().map_err(|_| anyhow!("pubkey {s} is not 32 bytes"))
+}
+
+fn parse_hash(s: &str) -> Result<[u8; 32]> {
+    let bytes = hex::decode(s).with_context(|| format!("invalid hash {s}"))?;
+    bytes.try_into().map_err(|_| anyhow!("hash {s} is not 32 bytes"))
+}
+
+pub fn leaf_hash(hasher: &SnapshotHasher<Sha256>, address: &Address, amount: u64, leaf_index: u32) -> [u8; 32] {
+    match address {
+        Address::Solana(wallet) => hasher.wallet_leaf(wallet, amount, leaf_index),
+        Address::Evm(evm_address) => hasher.evm_leaf(evm_address, amount, leaf_index),
+    }
+}
+
+/// Build the tree over `holders` in order and collect every proof
+pub fn build(holders: &[Holder], params: &HashParams) -> Result<Snapshot> {
+    let hasher = params.hasher();
+    let leaves: Vec<[u8; 32]> = holders
+        .iter()
+        .enumerate()
+        .map(|(index, holder)| leaf_hash(&hasher, &holder.address, holder.amount, index as u32))
+        .collect();
+    let tree = MerkleTree::new(&hasher, leaves).ok_or_else(|| anyhow!("holder list is empty"))?;
+
+    let claims = holders
+        .iter()
+        .enumerate()
+        .map(|(index, holder)| Claim {
+            address: holder.address.to_string(),
+            amount: holder.amount.to_string(),
+            leaf_index: index as u32,
+            leaf: hex::encode(tree.leaves()[index]),
+            proof: tree.proof(index).unwrap_or_default().iter().map(hex::encode).collect(),
+        })
+        .collect();
+    let total_amount: u128 = holders.iter().map(|holder| holder.amount as u128).sum();
+
+    Ok(Snapshot {
+        hash_version: params.hash_version,
+        program_id: bs58::encode(params.program_id).into_string(),
+        migration: bs58::encode(params.migration).into_string(),
+        root: hex::encode(tree.root()),
+        total_amount: total_amount.to_string(),
+        claims,
+    })
+}
+
+impl Snapshot {
+    pub fn params(&self) -> Result<HashParams> {
+        Ok(HashParams {
+            hash_version: self.hash_version,
+            program_id: parse_pubkey(&self.program_id)?,
+            migration: parse_pubkey(&self.migration)?,
+        })
+    }
+
+    /// Look up a holder's claim; EVM addresses match case-insensitively
+    pub fn claim(&self, address: &str) -> Result<&Claim> {
+        let address = address.parse::<Address>()?.to_string();
+        self.claims
+            .iter()
+            .find(|claim| claim.address == address)
+            .ok_or_else(|| anyhow!("{address} is not in the snapshot"))
+    }
+
+    /// Recompute every leaf from its address, amount and index and check its
+    /// proof against the root
+    pub fn verify(&self) -> Result<()> {
+        let hasher = self.params()?.hasher();
+        let root = parse_hash(&self.root)?;
+        let mut total_amount = 0u128;
+        for (position, claim) in self.claims.iter().enumerate() {
+            let address: Address = claim.address.parse()?;
+            let amount: u64 = claim
+                .amount
+                .parse()
+                .with_context(|| format!("{}: invalid amount {:?}", claim.address, claim.amount))?;
+            if claim.leaf_index as usize != position {
+                bail!("{}: leaf_index {} out of order", claim.address, claim.leaf_index);
+            }
+            let leaf = leaf_hash(&hasher, &address, amount, claim.leaf_index);
+            if hex::encode(leaf) != claim.leaf {
+                bail!("{}: leaf does not match address, amount and index", claim.address);
+            }
+            let proof = claim.proof.iter().map(|node| parse_hash(node)).collect::<Result<Vec<_>>>()?;
+            if !hasher.verify(leaf, &proof, &root) {
+                bail!("{}: proof does not verify against the root", claim.address);
+            }
+            total_amount += amount as u128;
+        }
+        if total_amount.to_string() != self.total_amount {
+            bail!("total_amount {} does not match the claims ({total_amount})", self.total_amount);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+use std::collections::HashSet;
+
+use necrobridge_cli::holders::parse_csv;
+use necrobridge_cli::manifest::{apply_exclusions, Manifest, SnapshotRules};
+use necrobridge_cli::replay::{parse_transfers, replay, to_holders, ReplayOptions};
+use necrobridge_cli::snapshot::{build, HashParams, HashVersion, Snapshot};
+
+const TOKEN: &str = "0x5fbdb2315678afecb367f032d93f642f64180aa3";
+const ALICE: &str = "0x1111111111111111111111111111111111111111";
+const BOB: &str = "0x2222222222222222222222222222222222222222";
+const LP: &str = "0x4444444444444444444444444444444444444444";
+const ZERO: &str = "0x0000000000000000000000000000000000000000";
+
+fn evm(address: &str) -> [u8; 20] {
+    hex::decode(&address[2..]).unwrap().try_into().unwrap()
+}
+
+fn logs() -> String {
+    [
+        (10, 0, ZERO, ALICE, 5_000_000_000_000u128),
+        (11, 0, ALICE, BOB, 1_500_000_000_000),
+        (11, 1, ALICE, LP, 2_000_000_000_000),
+        (15, 0, BOB, ALICE, 1_000_000_000_000),
+    ]
+    .iter()
+    .map(|(block, index, from, to, value)| {
+        format!(
+            r#"{{"block_number":{block},"log_index":{index},"token":"{TOKEN}","from":"{from}","to":"{to}","value":"{value}"}}"#
+        )
+    })
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+fn params() -> HashParams {
+    HashParams { hash_version: HashVersion::V1, program_id: [1u8; 32], migration: [2u8; 32] }
+}
+
+fn rules() -> SnapshotRules {
+    SnapshotRules {
+        source_chain: 2,
+        source_token: evm(TOKEN),
+        block_height: 12,
+        exclusions: HashSet::from([evm(LP)]),
+        scale_down: 3,
+    }
+}
+
+/// What `snapshot build --transfers ... --manifest` produces
+fn replayed_snapshot(rules: &SnapshotRules) -> Snapshot {
+    let transfers = parse_transfers(&logs()).unwrap();
+    let options = ReplayOptions { block: rules.block_height, token: Some(rules.source_token), exclude: rules.exclusions.clone() };
+    let balances = replay(&transfers, &options).unwrap();
+    let report = to_holders(&balances, &rules.exclusions, rules.scale_down).unwrap();
+    build(&report.holders, &params()).unwrap()
+}
+
+#[test]
+fn test_manifest_verifies_from_transfer_logs() {
+    let snapshot = replayed_snapshot(&rules());
+    let manifest = Manifest::new(&rules(), &snapshot);
+    assert_eq!(manifest.leaf_count, 2);
+    assert_eq!(manifest.total_amount, "3000000000");
+    assert_eq!(manifest.root, snapshot.root);
+
+    let rebuilt = manifest.verify_transfers(&parse_transfers(&logs()).unwrap()).unwrap();
+    assert_eq!(rebuilt, snapshot);
+    println!("✓ Manifest rebuilds the same root from raw logs");
+}
+
+#[test]
+fn test_manifest_rejects_mismatched_rules() {
+    let transfers = parse_transfers(&logs()).unwrap();
+    let manifest = Manifest::new(&rules(), &replayed_snapshot(&rules()));
+
+    let mut later_block = manifest.clone();
+    later_block.block_height = 15;
+    assert!(later_block.verify_transfers(&transfers).is_err());
+
+    let mut no_exclusions = manifest.clone();
+    no_exclusions.exclusions.clear();
+    assert!(no_exclusions.verify_transfers(&transfers).is_err());
+
+    let mut rescaled = manifest.clone();
+    rescaled.scale_down = 0;
+    assert!(rescaled.verify_transfers(&transfers).is_err());
+
+    let mut miscounted = manifest.clone();
+    miscounted.leaf_count = 3;
+    assert!(miscounted.verify_transfers(&transfers).is_err());
+
+    let mut other_version = manifest.clone();
+    other_version.hash_version = HashVersion::Legacy;
+    assert!(other_version.verify_transfers(&transfers).is_err());
+
+    // Fields that don't change the tree still change the committed hash
+    let mut other_chain = manifest.clone();
+    other_chain.source_chain = 10;
+    assert_ne!(other_chain.hash().unwrap(), manifest.hash().unwrap());
+    println!("✓ Manifests that don't describe the snapshot are rejected");
+}
+
+#[test]
+fn test_manifest_hash_is_canonical() {
+    let manifest = Manifest::new(&rules(), &replayed_snapshot(&rules()));
+    let mut reformatted = manifest.clone();
+    reformatted.source_token = TOKEN.to_uppercase().replacen("0X", "0x", 1);
+    reformatted.exclusions = vec![LP.to_uppercase().replacen("0X", "0x", 1), LP.to_string()];
+    reformatted.root = manifest.root.to_uppercase();
+    assert_eq!(reformatted.hash().unwrap(), manifest.hash().unwrap());
+
+    // Pretty-printed files hash the same as compact ones
+    let pretty: Manifest = serde_json::from_str(&serde_json::to_string_pretty(&manifest).unwrap()).unwrap();
+    assert_eq!(pretty.hash().unwrap(), manifest.hash().unwrap());
+
+    let mut future = manifest;
+    future.version = 2;
+    assert!(future.hash().is_err());
+    println!("✓ Address casing, ordering and whitespace don't change the hash");
+}
+
+#[test]
+fn test_canonical_encoding_is_stable() {
+    let csv = format!("address,amount\n{ALICE},999999999000\n{BOB},999\n");
+    let snapshot = build(&parse_csv(&csv).unwrap(), &params()).unwrap();
+    let rules = SnapshotRules {
+        source_chain: 2,
+        source_token: evm(TOKEN),
+        block_height: 11,
+        exclusions: HashSet::from([evm("0x000000000000000000000000000000000000dead")]),
+        scale_down: 9,
+    };
+    let manifest = Manifest::new(&rules, &snapshot);
+
+    let expected = concat!(
+        r#"{"version":1,"source_chain":2,"source_token":"0x5fbdb2315678afecb367f032d93f642f64180aa3","#,
+        r#""block_height":11,"exclusions":["0x000000000000000000000000000000000000dead"],"scale_down":9,"#,
+        r#""leaf_count":2,"total_amount":"999999999999","hash_version":"v1","#,
+        r#""program_id":"4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi","migration":"8qbHbw2BbbTHBW1sbeqakYXVKRQM8Ne7pLK7m6CVfeR","#,
+        r#""root":"ba09d87fd021261037167258c6c2118f9f317698d7fd94135469717e839f95f6"}"#
+    );
+    assert_eq!(String::from_utf8(manifest.canonical_bytes().unwrap()).unwrap(), expected);
+    assert_eq!(
+        hex::encode(manifest.hash().unwrap()),
+        "e8d8a84ddeba821d70df0ca1f1e793b43f7cfc8ddc717a018911b790124c2ee6"
+    );
+    println!("✓ Canonical manifest bytes and hash are pinned");
+}
+
+#[test]
+fn test_manifest_verifies_from_holder_list() {
+    let csv = format!("address,amount\n{ALICE},100\n{LP},900\n{BOB},50\n");
+    let mut rules = rules();
+    rules.scale_down = 0;
+    let holders = apply_exclusions(&parse_csv(&csv).unwrap(), &rules.exclusions);
+    assert_eq!(holders.len(), 2);
+
+    let manifest = Manifest::new(&rules, &build(&holders, &params()).unwrap());
+    manifest.verify_holders(&parse_csv(&csv).unwrap()).unwrap();
+
+    let mut scaled = manifest;
+    scaled.scale_down = 3;
+    assert!(scaled.verify_holders(&parse_csv(&csv).unwrap()).is_err());
+    println!("✓ Holder-list manifests apply exclusions when rebuilding");
+}
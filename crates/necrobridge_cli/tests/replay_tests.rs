@@ -0,0 +1,163 @@
+use std::collections::HashSet;
+
+use necrobridge_cli::holders::Address;
+use necrobridge_cli::replay::{parse_exclusions, parse_transfers, replay, to_holders, ReplayOptions, TRANSFER_TOPIC};
+use necrobridge_cli::snapshot::{build, HashParams, HashVersion};
+use sha3::{Digest, Keccak256};
+
+const ZERO: &str = "0x0000000000000000000000000000000000000000";
+const TOKEN: &str = "0x5fbdb2315678afecb367f032d93f642f64180aa3";
+const ALICE: &str = "0x1111111111111111111111111111111111111111";
+const BOB: &str = "0x2222222222222222222222222222222222222222";
+const CAROL: &str = "0x3333333333333333333333333333333333333333";
+const LP: &str = "0x4444444444444444444444444444444444444444";
+const DEAD: &str = "0x000000000000000000000000000000000000dead";
+
+fn evm(address: &str) -> [u8; 20] {
+    hex::decode(&address[2..]).unwrap().try_into().unwrap()
+}
+
+fn decoded(block: u64, log_index: u64, from: &str, to: &str, value: u128) -> String {
+    format!(r#"{{"block_number":{block},"log_index":{log_index},"from":"{from}","to":"{to}","value":"{value}"}}"#)
+}
+
+/// A log as hardhat's JSON-RPC returns it in `receipt.logs`
+fn raw(block: u64, log_index: u64, from: &str, to: &str, value: u128) -> String {
+    let topic = |address: &str| format!("0x{:0>64}", &address[2..]);
+    format!(
+        r#"{{"address":"{TOKEN}","topics":["{TRANSFER_TOPIC}","{}","{}"],"data":"0x{:064x}","blockNumber":"0x{block:x}","logIndex":"0x{log_index:x}","transactionHash":"0x00","removed":false}}"#,
+        topic(from),
+        topic(to),
+        value
+    )
+}
+
+/// Mint to Alice, spread to Bob, Carol, the LP and a burn address
+fn history(log: fn(u64, u64, &str, &str, u128) -> String) -> String {
+    [
+        log(10, 0, ZERO, ALICE, 1_000_000),
+        log(11, 0, ALICE, BOB, 250_000),
+        log(11, 1, ALICE, LP, 300_000),
+        log(12, 3, BOB, CAROL, 50_000),
+        log(12, 4, ALICE, DEAD, 10_000),
+        log(13, 0, ALICE, ZERO, 40_000),
+        log(20, 0, CAROL, BOB, 50_000),
+    ]
+    .join("\n")
+}
+
+#[test]
+fn test_transfer_topic_is_keccak_of_signature() {
+    let topic = Keccak256::digest(b"Transfer(address,address,uint256)");
+    assert_eq!(format!("0x{}", hex::encode(topic)), TRANSFER_TOPIC);
+    println!("✓ Transfer topic matches the event signature");
+}
+
+#[test]
+fn test_replay_balances_at_block() {
+    let transfers = parse_transfers(&history(decoded)).unwrap();
+
+    let at_13 = replay(&transfers, &ReplayOptions { block: 13, ..Default::default() }).unwrap();
+    assert_eq!(at_13[&evm(ALICE)], 400_000);
+    assert_eq!(at_13[&evm(BOB)], 200_000);
+    assert_eq!(at_13[&evm(CAROL)], 50_000);
+    assert_eq!(at_13[&evm(LP)], 300_000);
+    assert_eq!(at_13[&evm(DEAD)], 10_000);
+    assert!(!at_13.contains_key(&evm(ZERO)));
+    // Burns leave supply at mint - burn
+    assert_eq!(at_13.values().sum::<u128>(), 960_000);
+
+    // Carol's transfer at block 20 is after the snapshot
+    let at_12 = replay(&transfers, &ReplayOptions { block: 12, ..Default::default() }).unwrap();
+    assert_eq!(at_12[&evm(ALICE)], 440_000);
+    let at_20 = replay(&transfers, &ReplayOptions { block: 20, ..Default::default() }).unwrap();
+    assert!(!at_20.contains_key(&evm(CAROL)));
+    println!("✓ Replay stops at the snapshot block");
+}
+
+#[test]
+fn test_raw_and_decoded_logs_agree() {
+    let options = ReplayOptions { block: 20, ..Default::default() };
+    let from_raw = replay(&parse_transfers(&history(raw)).unwrap(), &options).unwrap();
+    let from_decoded = replay(&parse_transfers(&history(decoded)).unwrap(), &options).unwrap();
+    assert_eq!(from_raw, from_decoded);
+
+    // Same logs as a JSON array, shuffled; replay orders by block and log index
+    let mut lines: Vec<String> = history(raw).lines().map(str::to_string).collect();
+    lines.reverse();
+    let array = format!("[{}]", lines.join(","));
+    assert_eq!(replay(&parse_transfers(&array).unwrap(), &options).unwrap(), from_raw);
+    println!("✓ Raw RPC logs and decoded transfers replay the same");
+}
+
+#[test]
+fn test_raw_logs_filtering() {
+    let approval = format!(
+        r#"{{"address":"{TOKEN}","topics":["0x8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925","0x{:0>64}","0x{:0>64}"],"data":"0x{:064x}","blockNumber":"0xa","logIndex":"0x5"}}"#,
+        &ALICE[2..],
+        &BOB[2..],
+        5
+    );
+    let removed = raw(10, 6, ALICE, BOB, 5).replace(r#""removed":false"#, r#""removed":true"#);
+    let other_token = raw(10, 7, ALICE, BOB, 5).replace(TOKEN, CAROL);
+    let logs = [raw(10, 0, ZERO, ALICE, 100), approval, removed, other_token].join("\n");
+
+    let transfers = parse_transfers(&logs).unwrap();
+    assert_eq!(transfers.len(), 2);
+
+    let options = ReplayOptions { block: 10, token: Some(evm(TOKEN)), ..Default::default() };
+    let balances = replay(&transfers, &options).unwrap();
+    assert_eq!(balances[&evm(ALICE)], 100);
+    assert!(!balances.contains_key(&evm(BOB)));
+    println!("✓ Non-Transfer, removed and foreign-token logs are skipped");
+}
+
+#[test]
+fn test_replay_rejects_incomplete_exports() {
+    // Bob spends tokens he never received
+    let logs = [decoded(1, 0, ZERO, ALICE, 10), decoded(2, 0, BOB, CAROL, 5)].join("\n");
+    let err = replay(&parse_transfers(&logs).unwrap(), &ReplayOptions { block: 2, ..Default::default() }).unwrap_err();
+    assert!(err.to_string().contains("missing"));
+
+    let duplicated = [decoded(1, 0, ZERO, ALICE, 10), decoded(1, 0, ZERO, ALICE, 10)].join("\n");
+    assert!(replay(&parse_transfers(&duplicated).unwrap(), &ReplayOptions { block: 1, ..Default::default() }).is_err());
+
+    let too_big = raw(1, 0, ZERO, ALICE, 0).replace(&format!("0x{:064x}", 0), &format!("0x{}", "ff".repeat(32)));
+    assert!(parse_transfers(&too_big).is_err());
+    println!("✓ Spending more than the balance, duplicate logs and oversized values fail");
+}
+
+#[test]
+fn test_exclusions_and_scaling_feed_merkle_builder() {
+    let transfers = parse_transfers(&history(raw)).unwrap();
+    let balances = replay(&transfers, &ReplayOptions { block: 13, ..Default::default() }).unwrap();
+    let exclude: HashSet<[u8; 20]> = parse_exclusions(&format!("# burn\n{DEAD}\n\n{LP}  # source LP\n")).unwrap();
+
+    let report = to_holders(&balances, &exclude, 0).unwrap();
+    assert_eq!(report.excluded, 310_000);
+    let addresses: Vec<Address> = report.holders.iter().map(|holder| holder.address).collect();
+    assert_eq!(addresses, vec![Address::Evm(evm(ALICE)), Address::Evm(evm(BOB)), Address::Evm(evm(CAROL))]);
+    assert_eq!(report.holders.iter().map(|holder| holder.amount).sum::<u64>(), 650_000);
+
+    // Scaled down by 10^5: remainders become dust
+    let scaled = to_holders(&balances, &exclude, 5).unwrap();
+    assert_eq!(scaled.holders.iter().map(|holder| holder.amount).collect::<Vec<_>>(), vec![4, 2]);
+    assert_eq!(scaled.dust, 50_000);
+    assert_eq!(scaled.dropped, 1);
+
+    let params = HashParams { hash_version: HashVersion::V1, program_id: [1u8; 32], migration: [2u8; 32] };
+    let snapshot = build(&report.holders, &params).unwrap();
+    snapshot.verify().unwrap();
+    assert_eq!(snapshot.claim(BOB).unwrap().amount, "200000");
+    assert!(snapshot.claim(LP).is_err());
+    println!("✓ Excluded addresses are dropped and replayed holders build a snapshot");
+}
+
+#[test]
+fn test_to_holders_rejects_u64_overflow() {
+    let logs = decoded(1, 0, ZERO, ALICE, u64::MAX as u128 + 1);
+    let balances = replay(&parse_transfers(&logs).unwrap(), &ReplayOptions { block: 1, ..Default::default() }).unwrap();
+    assert!(to_holders(&balances, &HashSet::new(), 0).is_err());
+    assert_eq!(to_holders(&balances, &HashSet::new(), 1).unwrap().holders.len(), 1);
+    println!("✓ Balances must fit u64 after scaling");
+}
@@ -0,0 +1,122 @@
+use necrobridge_cli::holders::{parse_csv, parse_json, Address, Holder};
+use necrobridge_cli::snapshot::{build, parse_pubkey, HashParams, HashVersion, NECRO_MIGRATE_PROGRAM_ID};
+use serde_json::Value;
+
+const VECTORS: &str = include_str!("../../necro_merkle/vectors/snapshot_vectors.json");
+
+const WALLET_A: &str = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+const WALLET_B: &str = "BPFLoaderUpgradeab1e11111111111111111111111";
+
+fn params() -> HashParams {
+    HashParams {
+        hash_version: HashVersion::V1,
+        program_id: parse_pubkey(NECRO_MIGRATE_PROGRAM_ID).unwrap(),
+        migration: [3u8; 32],
+    }
+}
+
+#[test]
+fn test_parse_csv_and_json() {
+    let csv = format!("address,amount\n{WALLET_A}, 1000\n0xAbCdEf0123456789abcdef0123456789ABCDEF01,2500\n");
+    let json = format!(
+        r#"[{{"address":"{WALLET_A}","amount":1000}},{{"address":"0xabcdef0123456789abcdef0123456789abcdef01","amount":"2500"}}]"#
+    );
+
+    let from_csv = parse_csv(&csv).unwrap();
+    assert_eq!(from_csv, parse_json(&json).unwrap());
+    assert!(matches!(from_csv[0].address, Address::Solana(_)));
+    assert_eq!(from_csv[1].address.to_string(), "0xabcdef0123456789abcdef0123456789abcdef01");
+    println!("✓ CSV and JSON holder lists parse the same");
+}
+
+#[test]
+fn test_rejects_bad_holder_lists() {
+    let duplicate = format!("address,amount\n{WALLET_A},1\n{WALLET_A},2\n");
+    assert!(parse_csv(&duplicate).unwrap_err().to_string().contains("duplicate"));
+
+    let zero = format!("address,amount\n{WALLET_A},0\n");
+    assert!(parse_csv(&zero).unwrap_err().to_string().contains("zero amount"));
+
+    assert!(parse_csv("address,amount\nnot-an-address,1\n").is_err());
+    assert!(parse_csv("address,amount\n0x1234,1\n").is_err());
+    assert!(parse_csv(&format!("address,amount\n{WALLET_A},-1\n")).is_err());
+    assert!(parse_csv("address,amount\n").is_err());
+    println!("✓ Duplicate, zero and malformed rows are rejected");
+}
+
+#[test]
+fn test_build_and_verify() {
+    let holders = parse_csv(&format!("address,amount\n{WALLET_A},1000\n{WALLET_B},2000\n0x{},3000\n", "11".repeat(20))).unwrap();
+    let snapshot = build(&holders, &params()).unwrap();
+
+    assert_eq!(snapshot.claims.len(), 3);
+    assert_eq!(snapshot.total_amount, "6000");
+    snapshot.verify().unwrap();
+
+    let claim = snapshot.claim(WALLET_B).unwrap();
+    assert_eq!(claim.leaf_index, 1);
+    assert_eq!(claim.amount, "2000");
+    assert!(snapshot.claim(&format!("0x{}", "11".repeat(20))).is_ok());
+    assert!(snapshot.claim(&format!("0x{}", "22".repeat(20))).is_err());
+    println!("✓ Built snapshots verify and proofs can be looked up");
+}
+
+#[test]
+fn test_verify_catches_tampering() {
+    let holders = parse_csv(&format!("address,amount\n{WALLET_A},1000\n{WALLET_B},2000\n")).unwrap();
+    let snapshot = build(&holders, &params()).unwrap();
+
+    let mut inflated = snapshot.clone();
+    inflated.claims[0].amount = "1001".to_string();
+    assert!(inflated.verify().is_err());
+
+    let mut rebound = snapshot.clone();
+    rebound.migration = WALLET_B.to_string();
+    assert!(rebound.verify().is_err());
+
+    let mut bad_proof = snapshot.clone();
+    bad_proof.claims[1].proof[0] = "00".repeat(32);
+    assert!(bad_proof.verify().is_err());
+
+    let mut bad_total = snapshot;
+    bad_total.total_amount = "1".to_string();
+    assert!(bad_total.verify().is_err());
+    println!("✓ verify rejects edited amounts, migrations, proofs and totals");
+}
+
+#[test]
+fn test_matches_shared_vectors() {
+    let vectors: Value = serde_json::from_str(VECTORS).unwrap();
+    let mut checked = 0;
+    for case in vectors["cases"].as_array().unwrap() {
+        let leaves = case["leaves"].as_array().unwrap();
+        // Vesting leaves aren't part of holder lists
+        if leaves.iter().any(|leaf| leaf["kind"] == "vesting") {
+            continue;
+        }
+        let holders: Vec<Holder> = leaves
+            .iter()
+            .map(|leaf| {
+                let address = match leaf["kind"].as_str().unwrap() {
+                    "wallet" => Address::Solana(hex::decode(leaf["wallet"].as_str().unwrap()).unwrap().try_into().unwrap()),
+                    _ => Address::Evm(hex::decode(leaf["evm_address"].as_str().unwrap()).unwrap().try_into().unwrap()),
+                };
+                Holder { address, amount: leaf["amount"].as_str().unwrap().parse().unwrap() }
+            })
+            .collect();
+        let params = HashParams {
+            hash_version: serde_json::from_value(case["hash_version"].clone()).unwrap(),
+            program_id: hex::decode(case["program_id"].as_str().unwrap()).unwrap().try_into().unwrap(),
+            migration: hex::decode(case["migration"].as_str().unwrap()).unwrap().try_into().unwrap(),
+        };
+
+        let snapshot = build(&holders, &params).unwrap();
+        assert_eq!(snapshot.root, case["root"].as_str().unwrap(), "{}", case["name"]);
+        for (claim, leaf) in snapshot.claims.iter().zip(leaves) {
+            assert_eq!(serde_json::to_value(&claim.proof).unwrap(), leaf["proof"]);
+        }
+        checked += 1;
+    }
+    assert!(checked > 0);
+    println!("✓ Snapshots match {checked} shared vector cases");
+}
@@ -0,0 +1,116 @@
+// Adapters for seeding the DAO liquidity pool through an external AMM.
+//
+// `seed_dao_pool` passes the generic side of the deposit (depositor, sources,
+// LP mint and destination) and forwards its remaining accounts untouched; each
+// adapter knows how its AMM wants those laid out and builds the CPI. Adding an
+// AMM means implementing `AmmAdapter` and registering it in `adapter_for`, so
+// the program never CPIs into an arbitrary caller-supplied program.
+//
+// The only adapter so far is for `test_amm`, behind the default `test-amm`
+// feature and never registered when `mainnet` is set. Mainnet builds
+// (`--no-default-features --features mainnet`) therefore reject every AMM with
+// `UnsupportedAmm` until an adapter for a production AMM is added.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+#[cfg(feature = "test-amm")]
+use anchor_lang::{solana_program::instruction::AccountMeta, InstructionData};
+
+use crate::ErrorCode;
+
+pub struct DepositAccounts<'a, 'info> {
+    /// DAOLiquidity PDA; owns both sources and signs the CPI
+    pub depositor: &'a AccountInfo<'info>,
+    pub token_mint: &'a AccountInfo<'info>,
+    pub quote_mint: &'a AccountInfo<'info>,
+    pub token_source: &'a AccountInfo<'info>,
+    pub quote_source: &'a AccountInfo<'info>,
+    pub lp_mint: &'a AccountInfo<'info>,
+    pub lp_destination: &'a AccountInfo<'info>,
+    pub token_program: &'a AccountInfo<'info>,
+    /// AMM-specific accounts, in the order the adapter documents
+    pub pool_accounts: &'a [AccountInfo<'info>],
+}
+
+pub trait AmmAdapter {
+    fn program_id(&self) -> Pubkey;
+
+    fn deposit_instruction(
+        &self,
+        accounts: &DepositAccounts,
+        token_amount: u64,
+        quote_amount: u64,
+        min_lp: u64,
+    ) -> Result<Instruction>;
+}
+
+/// Look up the adapter for an AMM program; unknown programs are rejected
+pub fn adapter_for(program_id: &Pubkey) -> Result<&'static dyn AmmAdapter> {
+    #[cfg(all(feature = "test-amm", not(feature = "mainnet")))]
+    if *program_id == test_amm::ID {
+        return Ok(&TestAmmAdapter);
+    }
+    msg!("No AMM adapter for {}", program_id);
+    err!(ErrorCode::UnsupportedAmm)
+}
+
+/// In-repo constant-product AMM used on localnet and devnet.
+///
+/// Pool accounts: `[pool, vault_a, vault_b]`. Either pool side may hold the
+/// migrated token; the adapter reads the pool to order the deposit.
+#[cfg(feature = "test-amm")]
+pub struct TestAmmAdapter;
+
+#[cfg(feature = "test-amm")]
+impl AmmAdapter for TestAmmAdapter {
+    fn program_id(&self) -> Pubkey {
+        test_amm::ID
+    }
+
+    fn deposit_instruction(
+        &self,
+        accounts: &DepositAccounts,
+        token_amount: u64,
+        quote_amount: u64,
+        min_lp: u64,
+    ) -> Result<Instruction> {
+        let [pool, vault_a, vault_b] = accounts.pool_accounts else {
+            return err!(ErrorCode::InvalidAmmAccounts);
+        };
+        require!(*pool.owner == test_amm::ID, ErrorCode::InvalidAmmAccounts);
+        let pool_state = test_amm::Pool::try_deserialize(&mut &pool.try_borrow_data()?[..])?;
+        require!(pool_state.lp_mint == accounts.lp_mint.key(), ErrorCode::InvalidAmmAccounts);
+
+        let token_is_a = if pool_state.mint_a == accounts.token_mint.key() && pool_state.mint_b == accounts.quote_mint.key() {
+            true
+        } else if pool_state.mint_a == accounts.quote_mint.key() && pool_state.mint_b == accounts.token_mint.key() {
+            false
+        } else {
+            return err!(ErrorCode::InvalidAmmAccounts);
+        };
+
+        let (mint_a, mint_b, depositor_a, depositor_b, amount_a, amount_b) = if token_is_a {
+            (accounts.token_mint, accounts.quote_mint, accounts.token_source, accounts.quote_source, token_amount, quote_amount)
+        } else {
+            (accounts.quote_mint, accounts.token_mint, accounts.quote_source, accounts.token_source, quote_amount, token_amount)
+        };
+
+        Ok(Instruction {
+            program_id: test_amm::ID,
+            accounts: vec![
+                AccountMeta::new_readonly(accounts.depositor.key(), true),
+                AccountMeta::new_readonly(pool.key(), false),
+                AccountMeta::new_readonly(mint_a.key(), false),
+                AccountMeta::new_readonly(mint_b.key(), false),
+                AccountMeta::new(vault_a.key(), false),
+                AccountMeta::new(vault_b.key(), false),
+                AccountMeta::new(accounts.lp_mint.key(), false),
+                AccountMeta::new(depositor_a.key(), false),
+                AccountMeta::new(depositor_b.key(), false),
+                AccountMeta::new(accounts.lp_destination.key(), false),
+                AccountMeta::new_readonly(accounts.token_program.key(), false),
+            ],
+            data: test_amm::instruction::AddLiquidity { amount_a, amount_b, min_lp }.data(),
+        })
+    }
+}
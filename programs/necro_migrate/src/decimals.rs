@@ -0,0 +1,56 @@
+// Conversion from snapshot amounts (base units at `source_decimals`) to base
+// units of the migrated mint (`decimals`).
+//
+// Rounding rules:
+// - More source decimals: divide by 10^(source - local) and round down. The
+//   remainder, in source units, is returned as dust so that
+//   `local * 10^(source - local) + dust == amount` always holds. Rounding
+//   never pays out more than the snapshot allows.
+// - Fewer source decimals: multiply by 10^(local - source). This is exact, and
+//   the claim fails with `DecimalOverflow` if the result does not fit in a u64.
+// - Same decimals: amounts pass through unchanged.
+//
+// Leaves commit to u64 amounts, so `source_decimals` describes the snapshot,
+// not necessarily the source token. An 18-decimal ERC-20 can be snapshotted
+// at 9 decimals to keep balances in range.
+//
+// The two may differ by at most `MAX_DECIMALS_DIFF`. Past that, scaling down
+// leaves nearly every u64 amount as dust and scaling up overflows on all but
+// the smallest claims.
+
+use anchor_lang::prelude::*;
+
+use crate::ErrorCode;
+
+/// Wide enough for an 18-decimal ERC-20 migrating to a 0-decimal mint
+pub const MAX_DECIMALS_DIFF: u8 = 18;
+
+/// Checked at init so a migration can't be created with decimals no claim can use
+pub fn validate(source_decimals: u8, decimals: u8) -> Result<()> {
+    require!(source_decimals.abs_diff(decimals) <= MAX_DECIMALS_DIFF, ErrorCode::InvalidDecimals);
+    Ok(())
+}
+
+/// Returns `(local_amount, dust)` with dust in source base units
+pub fn rescale(amount: u64, source_decimals: u8, decimals: u8) -> Result<(u64, u64)> {
+    if source_decimals >= decimals {
+        let factor = match 10u128.checked_pow((source_decimals - decimals) as u32) {
+            Some(factor) => factor,
+            // Larger than any u64: everything is dust
+            None => return Ok((0, amount)),
+        };
+        let local = (amount as u128 / factor) as u64;
+        let dust = (amount as u128 % factor) as u64;
+        Ok((local, dust))
+    } else {
+        if amount == 0 {
+            return Ok((0, 0));
+        }
+        let local = 10u128
+            .checked_pow((decimals - source_decimals) as u32)
+            .and_then(|factor| factor.checked_mul(amount as u128))
+            .and_then(|local| u64::try_from(local).ok())
+            .ok_or(ErrorCode::DecimalOverflow)?;
+        Ok((local, 0))
+    }
+}
@@ -0,0 +1,91 @@
+// Checks that a transaction carries an Ed25519 precompile instruction signing
+// an expected message. The precompile itself verifies the signature; we only
+// need to confirm it verified the right key over the right bytes.
+//
+// Instruction data layout:
+//   num_signatures u8 | padding u8
+//   | per signature: signature_offset u16, signature_ix u16, public_key_offset u16,
+//     public_key_ix u16, message_offset u16, message_size u16, message_ix u16
+//   | signature / public key / message bytes
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+
+use crate::ErrorCode;
+
+const HEADER_LEN: usize = 2;
+const OFFSETS_LEN: usize = 14;
+const PUBKEY_LEN: usize = 32;
+const SIGNATURE_LEN: usize = 64;
+/// Instruction index meaning "data lives in the precompile instruction itself"
+const CURRENT_IX: u16 = u16::MAX;
+
+/// Message a beneficiary signs to let someone else submit their claim
+pub fn relayed_claim_message(
+    migration: &Pubkey,
+    amount: u64,
+    leaf_index: u32,
+    recipient_token_account: &Pubkey,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(32 + 8 + 4 + 32);
+    message.extend_from_slice(migration.as_ref());
+    message.extend_from_slice(&amount.to_le_bytes());
+    message.extend_from_slice(&leaf_index.to_le_bytes());
+    message.extend_from_slice(recipient_token_account.as_ref());
+    message
+}
+
+/// Load the instruction right before the current one and check it is an
+/// Ed25519 verification of `message` by `signer`
+pub fn verify_previous_instruction(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current = load_current_index_checked(instructions_sysvar)?;
+    require!(current > 0, ErrorCode::InvalidSignature);
+    let ix = load_instruction_at_checked(current as usize - 1, instructions_sysvar)?;
+    verify_instruction(&ix, signer, message)
+}
+
+pub fn verify_instruction(ix: &Instruction, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    require_keys_eq!(ix.program_id, ed25519_program::ID, ErrorCode::InvalidSignature);
+    require!(ix.accounts.is_empty(), ErrorCode::InvalidSignature);
+
+    let data = &ix.data;
+    require!(data.len() >= HEADER_LEN + OFFSETS_LEN, ErrorCode::InvalidSignature);
+    require!(data[0] == 1, ErrorCode::InvalidSignature);
+
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let offsets = HEADER_LEN;
+    let signature_offset = read_u16(offsets) as usize;
+    let signature_ix = read_u16(offsets + 2);
+    let public_key_offset = read_u16(offsets + 4) as usize;
+    let public_key_ix = read_u16(offsets + 6);
+    let message_offset = read_u16(offsets + 8) as usize;
+    let message_size = read_u16(offsets + 10) as usize;
+    let message_ix = read_u16(offsets + 12);
+
+    // Everything must come from this instruction, otherwise the precompile
+    // may have verified bytes we never look at
+    require!(
+        signature_ix == CURRENT_IX && public_key_ix == CURRENT_IX && message_ix == CURRENT_IX,
+        ErrorCode::InvalidSignature
+    );
+    require!(data.len() >= signature_offset + SIGNATURE_LEN, ErrorCode::InvalidSignature);
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + PUBKEY_LEN)
+        .ok_or(ErrorCode::InvalidSignature)?;
+    let signed_message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(ErrorCode::InvalidSignature)?;
+
+    require!(public_key == signer.as_ref(), ErrorCode::InvalidSignature);
+    require!(signed_message == message, ErrorCode::InvalidSignature);
+    Ok(())
+}
@@ -0,0 +1,79 @@
+// Ownership proofs for snapshot leaves that commit to an EVM address.
+//
+// The holder signs, with their Ethereum key, an EIP-191 `personal_sign` message
+// binding the claim to a Solana recipient:
+//
+//   "\x19Ethereum Signed Message:\n76" | migration | recipient | amount_le | leaf_index_le
+//
+// We recover the signer with the secp256k1_recover syscall and compare the
+// derived address against the one in the Merkle leaf.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+use anchor_lang::solana_program::secp256k1_recover::secp256k1_recover;
+
+use crate::ErrorCode;
+
+pub const CLAIM_MESSAGE_LEN: usize = 32 + 32 + 8 + 4;
+
+/// secp256k1 group order / 2; signatures with a larger `s` are malleable
+const HALF_ORDER: [u8; 32] = [
+    0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b, 0x20, 0xa0,
+];
+
+pub fn claim_message(
+    migration: &Pubkey,
+    recipient: &Pubkey,
+    amount: u64,
+    leaf_index: u32,
+) -> [u8; CLAIM_MESSAGE_LEN] {
+    let mut message = [0u8; CLAIM_MESSAGE_LEN];
+    message[..32].copy_from_slice(migration.as_ref());
+    message[32..64].copy_from_slice(recipient.as_ref());
+    message[64..72].copy_from_slice(&amount.to_le_bytes());
+    message[72..].copy_from_slice(&leaf_index.to_le_bytes());
+    message
+}
+
+/// keccak256 of the EIP-191 prefixed message, as produced by `personal_sign`
+pub fn eth_signed_message_hash(message: &[u8]) -> [u8; 32] {
+    let prefix = format!("\x19Ethereum Signed Message:\n{}", message.len());
+    keccak::hashv(&[prefix.as_bytes(), message]).to_bytes()
+}
+
+/// Ethereum address of an uncompressed (64 byte, no 0x04 tag) public key
+pub fn address_from_pubkey(pubkey: &[u8; 64]) -> [u8; 20] {
+    let hash = keccak::hash(pubkey).to_bytes();
+    hash[12..].try_into().unwrap()
+}
+
+/// Recover the signing address. Accepts `recovery_id` as 0/1 or Ethereum's 27/28.
+pub fn recover_address(hash: &[u8; 32], signature: &[u8; 64], recovery_id: u8) -> Result<[u8; 20]> {
+    let recovery_id = match recovery_id {
+        0 | 1 => recovery_id,
+        27 | 28 => recovery_id - 27,
+        _ => return err!(ErrorCode::InvalidSignature),
+    };
+    require!(signature[32..] <= HALF_ORDER[..], ErrorCode::InvalidSignature);
+
+    let pubkey = secp256k1_recover(hash, recovery_id, signature)
+        .map_err(|_| error!(ErrorCode::InvalidSignature))?;
+    Ok(address_from_pubkey(&pubkey.to_bytes()))
+}
+
+/// Check that `evm_address` signed off on sending its allocation to `recipient`
+pub fn verify_claim_signature(
+    evm_address: &[u8; 20],
+    migration: &Pubkey,
+    recipient: &Pubkey,
+    amount: u64,
+    leaf_index: u32,
+    signature: &[u8; 64],
+    recovery_id: u8,
+) -> Result<()> {
+    let message = claim_message(migration, recipient, amount, leaf_index);
+    let signer = recover_address(&eth_signed_message_hash(&message), signature, recovery_id)?;
+    require!(&signer == evm_address, ErrorCode::InvalidSignature);
+    Ok(())
+}
@@ -0,0 +1,140 @@
+// Quadratic voting helpers. A voter's weight is the integer square root of
+// their stake, so 100x the tokens only buys 10x the say.
+
+use anchor_lang::prelude::*;
+
+use crate::timelock::OperationKind;
+use crate::{DAOLiquidity, ErrorCode, Migration};
+
+/// Upper bound on actions per proposal, so the account size is fixed at creation
+pub const MAX_PROPOSAL_ACTIONS: usize = 4;
+
+/// floor(sqrt(n)) without floating point
+pub fn isqrt(n: u64) -> u64 {
+    if n < 2 {
+        return n;
+    }
+    // Newton's method from an initial guess that is always >= the root
+    let mut x = 1u64 << ((64 - n.leading_zeros()).div_ceil(2));
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+pub fn vote_weight(stake: u64) -> u64 {
+    isqrt(stake)
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProposalStatus {
+    Active,
+    Passed,
+    Rejected,
+    Executed,
+}
+
+/// Admin operations a passed proposal can perform on its own migration once
+/// governance holds the `Migration` authority
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProposalAction {
+    SetSnapshotRoot { root: [u8; 32], manifest_hash: [u8; 32] },
+    Unpause,
+    SetDaoTreasury { treasury: Pubkey },
+    SetPoolInitialized { initialized: bool },
+    /// Block a QueuedOperation; carried out by `veto_operation_by_vote`
+    VetoOperation { operation: Pubkey },
+    Finalize,
+    /// Creates the SnapshotEpoch account passed to `execute_proposal`
+    AddSnapshotEpoch {
+        snapshot_root: [u8; 32],
+        manifest_hash: [u8; 32],
+        supply_cap: u64,
+        claim_start: i64,
+        claim_end: i64,
+    },
+    /// Queue a timelocked operation as the admin would with `queue_operation`;
+    /// creates the QueuedOperation account passed to `execute_proposal`
+    QueueOperation { kind: OperationKind },
+    /// First step of handing the admin on; the new authority accepts it
+    ProposeAdmin { authority: Pubkey },
+}
+
+/// Checked by `create_proposal`. A veto runs through `veto_operation_by_vote`,
+/// which marks the whole proposal executed, so it can't share a proposal with
+/// actions meant for `execute_proposal`.
+pub fn validate_actions(actions: &[ProposalAction]) -> Result<()> {
+    require!(actions.len() <= MAX_PROPOSAL_ACTIONS, ErrorCode::TooManyActions);
+    let vetoes = actions
+        .iter()
+        .filter(|action| matches!(action, ProposalAction::VetoOperation { .. }))
+        .count();
+    require!(vetoes == 0 || actions.len() == 1, ErrorCode::VetoNotSoleAction);
+
+    // `execute_proposal` takes a single account for each of these
+    let queued = actions
+        .iter()
+        .filter(|action| matches!(action, ProposalAction::QueueOperation { .. }))
+        .count();
+    let epochs = actions
+        .iter()
+        .filter(|action| matches!(action, ProposalAction::AddSnapshotEpoch { .. }))
+        .count();
+    require!(queued <= 1 && epochs <= 1, ErrorCode::DuplicateAccountAction);
+
+    for action in actions {
+        match *action {
+            ProposalAction::QueueOperation { kind } => kind.validate()?,
+            ProposalAction::AddSnapshotEpoch { supply_cap, claim_start, claim_end, .. } => {
+                require!(supply_cap > 0, ErrorCode::InvalidAmount);
+                require!(claim_start < claim_end, ErrorCode::InvalidOperation);
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+impl ProposalAction {
+    /// Changes the migration timelock covers when the admin makes them. A
+    /// proposal carrying one only executes if the governance execution delay
+    /// is at least `timelock_delay`; otherwise queue it with `QueueOperation`.
+    pub fn is_timelocked(&self) -> bool {
+        matches!(
+            self,
+            ProposalAction::SetSnapshotRoot { .. }
+                | ProposalAction::SetDaoTreasury { .. }
+                | ProposalAction::Finalize
+                | ProposalAction::AddSnapshotEpoch { .. }
+        )
+    }
+
+    /// Treasury and pool actions need the migration's DAOLiquidity account.
+    /// Actions that create an account are carried out by `execute_proposal`.
+    pub fn apply(&self, migration: &mut Migration, dao_liquidity: Option<&mut DAOLiquidity>) -> Result<()> {
+        match *self {
+            ProposalAction::SetSnapshotRoot { root, manifest_hash } => migration.set_snapshot(root, manifest_hash),
+            ProposalAction::Unpause => migration.paused = false,
+            ProposalAction::SetDaoTreasury { treasury } => {
+                let dao_liquidity = dao_liquidity.ok_or(ErrorCode::InvalidOperation)?;
+                dao_liquidity.dao_treasury = treasury;
+                dao_liquidity.pending_treasury = Pubkey::default();
+            }
+            ProposalAction::SetPoolInitialized { initialized } => {
+                let dao_liquidity = dao_liquidity.ok_or(ErrorCode::InvalidOperation)?;
+                dao_liquidity.pool_initialized = initialized;
+            }
+            ProposalAction::Finalize => migration.is_active = false,
+            ProposalAction::ProposeAdmin { authority } => migration.pending_authority = authority,
+            // Vetoes run through `veto_operation_by_vote`, the others need
+            // the accounts `execute_proposal` creates
+            ProposalAction::VetoOperation { .. }
+            | ProposalAction::AddSnapshotEpoch { .. }
+            | ProposalAction::QueueOperation { .. } => return err!(ErrorCode::InvalidOperation),
+        }
+        Ok(())
+    }
+}
@@ -1,15 +1,36 @@
 declare_id!("2z3U1Wwq7bgHnkEuD5Yfw97g8uGyimDyRafRar21Bsva");
 
 // pub mod instructions; // Duplicate account structs - use definitions in lib.rs instead
+pub mod amm;
+pub mod decimals;
+pub mod ed25519;
+pub mod evm;
+pub mod governance;
+pub mod merkle;
+pub mod timelock;
+pub mod vesting;
+pub mod wormhole;
 
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenInterface, TokenAccount};
 use std::mem::size_of;
 
+use governance::{ProposalAction, ProposalStatus, MAX_PROPOSAL_ACTIONS};
+use merkle::{HashVersion, SnapshotHasher};
+use timelock::{OperationKind, OperationStatus};
+use vesting::VestingSchedule;
+use wormhole::{ClaimPayload, PostedVaa, CORE_BRIDGE_PROGRAM_ID};
+
+/// Bytes of claim status per ClaimBitmap chunk (one bit per leaf)
+pub const CLAIM_BITMAP_CHUNK_BYTES: usize = 4096;
+/// Leaves covered by a single ClaimBitmap chunk
+pub const CLAIM_BITMAP_CHUNK_LEAVES: u32 = (CLAIM_BITMAP_CHUNK_BYTES * 8) as u32;
+
 #[program]
 pub mod necro_migrate {
     use super::*;
 
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize_migration(
         ctx: Context<InitializeMigration>,
         name: [u8; 64],
@@ -17,7 +38,15 @@ pub mod necro_migrate {
         source_address: [u8; 32],
         snapshot_root: [u8; 32],
         total_supply: u64,
+        supply_mode: SupplyMode,
+        decimals: u8,
+        source_decimals: u8,
+        hash_version: HashVersion,
+        manifest_hash: [u8; 32],
+        claim_tracking: ClaimTracking,
     ) -> Result<()> {
+        decimals::validate(source_decimals, decimals)?;
+
         let migration = &mut ctx.accounts.migration;
         migration.name = name;
         migration.admin = ctx.accounts.admin.key();
@@ -28,227 +57,2092 @@ pub mod necro_migrate {
         migration.bump = ctx.bumps.migration;
         migration.migrated_amount = 0;
         migration.is_active = true;
+        migration.epoch_count = 0;
+        migration.claim_deadline = 0;
+        migration.guardian = ctx.accounts.admin.key();
+        migration.paused = false;
+        migration.authority = ctx.accounts.admin.key();
+        migration.pending_authority = Pubkey::default();
+        migration.timelock_delay = 0;
+        migration.operation_count = 0;
+        migration.mint = ctx.accounts.mint.key();
+        migration.token_vault = ctx.accounts.token_vault.key();
+        migration.authority_bump = ctx.bumps.migration_authority;
+        migration.supply_mode = supply_mode;
+        migration.decimals = decimals;
+        migration.source_decimals = source_decimals;
+        migration.dust = 0;
+        migration.hash_version = hash_version;
+        migration.manifest_hash = manifest_hash;
+        migration.claim_tracking = claim_tracking;
+
+        // Vault mode pre-mints the full supply; mint-on-claim mints as claims land
+        if supply_mode == SupplyMode::Vault {
+            let migration_key = migration.key();
+            let seeds = &[b"authority", migration_key.as_ref(), &[migration.authority_bump]];
+            let signer = &[&seeds[..]];
+            let cpi_accounts = anchor_spl::token_interface::MintTo {
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.token_vault.to_account_info(),
+                authority: ctx.accounts.migration_authority.to_account_info(),
+            };
+            anchor_spl::token_interface::mint_to(
+                CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer),
+                total_supply,
+            )?;
+        }
         
-        msg!("Migration initialized");
+        msg!(
+            "Migration initialized ({:?}, {} decimals from {})",
+            supply_mode,
+            decimals,
+            source_decimals
+        );
         Ok(())
     }
 
+    /// Claim against the initial snapshot (`epoch == 0`) or a later epoch
+    /// added with `add_snapshot_epoch`, passing its SnapshotEpoch account.
+    /// Like every claim path, pays out through `pay_claim`: if the migration
+    /// has a DAOLiquidity account, `lp_percentage` of the claim goes to
+    /// `dao_vault` and the user receives the rest.
     pub fn claim_tokens(
         ctx: Context<ClaimTokens>,
         amount: u64,
         merkle_proof: Vec<[u8; 32]>,
         leaf_index: u32,
+        epoch: u32,
     ) -> Result<()> {
-        let migration = &ctx.accounts.migration;
-        require!(migration.is_active, ErrorCode::MigrationNotActive);
+        let migration = &mut ctx.accounts.migration;
+        migration.check_claimable(Clock::get()?.unix_timestamp)?;
         require!(amount > 0, ErrorCode::InvalidAmount);
 
         let user_claim = &mut ctx.accounts.user_claim;
         require!(!user_claim.is_claimed, ErrorCode::AlreadyClaimed);
 
+        let snapshot_root = match ctx.accounts.snapshot_epoch.as_deref_mut() {
+            None => {
+                require!(epoch == 0, ErrorCode::InvalidEpoch);
+                migration.snapshot_root
+            }
+            Some(snapshot_epoch) => {
+                let now = Clock::get()?.unix_timestamp;
+                require!(snapshot_epoch.is_open(now), ErrorCode::ClaimWindowClosed);
+                snapshot_epoch.snapshot_root
+            }
+        };
+
         // Verify merkle proof
-        let leaf = anchor_lang::solana_program::hash::hashv(&[
-            ctx.accounts.user.key().as_ref(),
-            &amount.to_le_bytes(),
-            &leaf_index.to_le_bytes(),
-        ]);
-
-        let mut current_hash = leaf.to_bytes();
-        for proof_hash in merkle_proof {
-            current_hash = if current_hash < proof_hash {
-                anchor_lang::solana_program::hash::hashv(&[&current_hash, &proof_hash]).to_bytes()
-            } else {
-                anchor_lang::solana_program::hash::hashv(&[&proof_hash, &current_hash]).to_bytes()
-            };
+        let hasher = SnapshotHasher::new(migration.hash_version, migration.key());
+        let leaf = hasher.leaf_hash(&ctx.accounts.user.key(), amount, leaf_index);
+        require!(
+            hasher.verify(leaf, &merkle_proof, &snapshot_root),
+            ErrorCode::InvalidMerkleProof
+        );
+
+        // Everything below is in the migrated mint's base units
+        let amount = migration.settle_claim(amount)?;
+        if let Some(snapshot_epoch) = ctx.accounts.snapshot_epoch.as_deref_mut() {
+            snapshot_epoch.record_claim(amount)?;
         }
 
-        require!(current_hash == migration.snapshot_root, ErrorCode::InvalidMerkleProof);
+        let (net_amount, lp_amount) = pay_claim(
+            migration,
+            &ctx.accounts.token_program,
+            &ctx.accounts.token_vault,
+            &ctx.accounts.mint,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.migration_authority,
+            &ctx.accounts.dao_liquidity,
+            ctx.accounts.dao_vault.as_ref(),
+            amount,
+        )?;
 
         // Mark as claimed
         user_claim.user = ctx.accounts.user.key();
         user_claim.is_claimed = true;
         user_claim.amount = amount;
+        user_claim.net_amount = net_amount;
+
+        msg!(
+            "User {} claimed {} tokens ({} to DAO LP reserve)",
+            ctx.accounts.user.key(),
+            net_amount,
+            lp_amount
+        );
+        Ok(())
+    }
+
+    /// Claim submitted by a relayer on behalf of `beneficiary`, who authorizes it
+    /// off-chain with an Ed25519 signature over (migration, amount, leaf_index,
+    /// recipient token account). The relayer pays fees and the UserClaim rent.
+    pub fn claim_tokens_relayed(
+        ctx: Context<ClaimTokensRelayed>,
+        amount: u64,
+        merkle_proof: Vec<[u8; 32]>,
+        leaf_index: u32,
+    ) -> Result<()> {
+        let migration = &mut ctx.accounts.migration;
+        migration.check_claimable(Clock::get()?.unix_timestamp)?;
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        let beneficiary = ctx.accounts.beneficiary.key();
+        let message = ed25519::relayed_claim_message(
+            &migration.key(),
+            amount,
+            leaf_index,
+            &ctx.accounts.recipient_token_account.key(),
+        );
+        ed25519::verify_previous_instruction(&ctx.accounts.instructions, &beneficiary, &message)?;
+
+        let user_claim = &mut ctx.accounts.user_claim;
+        require!(!user_claim.is_claimed, ErrorCode::AlreadyClaimed);
+
+        let hasher = SnapshotHasher::new(migration.hash_version, migration.key());
+        let leaf = hasher.leaf_hash(&beneficiary, amount, leaf_index);
+        require!(
+            hasher.verify(leaf, &merkle_proof, &migration.snapshot_root),
+            ErrorCode::InvalidMerkleProof
+        );
+
+        let amount = migration.settle_claim(amount)?;
+        let (net_amount, lp_amount) = pay_claim(
+            migration,
+            &ctx.accounts.token_program,
+            &ctx.accounts.token_vault,
+            &ctx.accounts.mint,
+            &ctx.accounts.recipient_token_account,
+            &ctx.accounts.migration_authority,
+            &ctx.accounts.dao_liquidity,
+            ctx.accounts.dao_vault.as_ref(),
+            amount,
+        )?;
+        user_claim.user = beneficiary;
+        user_claim.is_claimed = true;
+        user_claim.amount = amount;
+        user_claim.net_amount = net_amount;
+
+        msg!(
+            "Relayer {} claimed {} tokens for {} ({} to DAO LP reserve)",
+            ctx.accounts.relayer.key(),
+            net_amount,
+            beneficiary,
+            lp_amount
+        );
+        Ok(())
+    }
+
+    /// Claim an allocation whose leaf commits to a 20-byte EVM address. The
+    /// holder proves control of that address with a secp256k1 signature that
+    /// binds the claim to `recipient` on Solana.
+    pub fn claim_tokens_evm(
+        ctx: Context<ClaimTokensEvm>,
+        evm_address: [u8; 20],
+        amount: u64,
+        merkle_proof: Vec<[u8; 32]>,
+        leaf_index: u32,
+        signature: [u8; 64],
+        recovery_id: u8,
+    ) -> Result<()> {
+        let migration = &mut ctx.accounts.migration;
+        migration.check_claimable(Clock::get()?.unix_timestamp)?;
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        let recipient = ctx.accounts.recipient.key();
+        evm::verify_claim_signature(
+            &evm_address,
+            &migration.key(),
+            &recipient,
+            amount,
+            leaf_index,
+            &signature,
+            recovery_id,
+        )?;
+
+        let hasher = SnapshotHasher::new(migration.hash_version, migration.key());
+        let leaf = hasher.evm_leaf_hash(&evm_address, amount, leaf_index);
+        require!(
+            hasher.verify(leaf, &merkle_proof, &migration.snapshot_root),
+            ErrorCode::InvalidMerkleProof
+        );
+
+        let user_claim = &mut ctx.accounts.user_claim;
+        require!(!user_claim.is_claimed, ErrorCode::AlreadyClaimed);
+        let amount = migration.settle_claim(amount)?;
+        let (net_amount, lp_amount) = pay_claim(
+            migration,
+            &ctx.accounts.token_program,
+            &ctx.accounts.token_vault,
+            &ctx.accounts.mint,
+            &ctx.accounts.recipient_token_account,
+            &ctx.accounts.migration_authority,
+            &ctx.accounts.dao_liquidity,
+            ctx.accounts.dao_vault.as_ref(),
+            amount,
+        )?;
+        user_claim.user = recipient;
+        user_claim.is_claimed = true;
+        user_claim.amount = amount;
+        user_claim.net_amount = net_amount;
+
+        msg!(
+            "EVM holder claimed {} tokens for {} ({} to DAO LP reserve)",
+            net_amount,
+            recipient,
+            lp_amount
+        );
+        Ok(())
+    }
+
+    /// Claim a vesting leaf. The allocation, after the DAO LP carve-out, moves
+    /// from the migration vault into an escrow owned by a per-beneficiary
+    /// VestingEscrow PDA and is released through `withdraw_vested`.
+    pub fn claim_vesting(
+        ctx: Context<ClaimVesting>,
+        amount: u64,
+        merkle_proof: Vec<[u8; 32]>,
+        leaf_index: u32,
+        schedule: VestingSchedule,
+    ) -> Result<()> {
+        let migration = &mut ctx.accounts.migration;
+        migration.check_claimable(Clock::get()?.unix_timestamp)?;
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        schedule.validate()?;
+
+        let hasher = SnapshotHasher::new(migration.hash_version, migration.key());
+        let leaf = hasher.vesting_leaf_hash(&ctx.accounts.user.key(), amount, leaf_index, &schedule);
+        require!(
+            hasher.verify(leaf, &merkle_proof, &migration.snapshot_root),
+            ErrorCode::InvalidMerkleProof
+        );
+
+        let amount = migration.settle_claim(amount)?;
+        let (net_amount, lp_amount) = pay_claim(
+            migration,
+            &ctx.accounts.token_program,
+            &ctx.accounts.token_vault,
+            &ctx.accounts.mint,
+            &ctx.accounts.escrow_vault,
+            &ctx.accounts.migration_authority,
+            &ctx.accounts.dao_liquidity,
+            ctx.accounts.dao_vault.as_ref(),
+            amount,
+        )?;
+        let escrow = &mut ctx.accounts.vesting_escrow;
+        escrow.migration = migration.key();
+        escrow.beneficiary = ctx.accounts.user.key();
+        escrow.total_amount = net_amount;
+        escrow.gross_amount = amount;
+        escrow.withdrawn = 0;
+        escrow.schedule = schedule;
+        escrow.bump = ctx.bumps.vesting_escrow;
+
+        msg!(
+            "User {} locked {} tokens in vesting ({} to DAO LP reserve)",
+            ctx.accounts.user.key(),
+            net_amount,
+            lp_amount
+        );
+        Ok(())
+    }
+
+    /// Release whatever has unlocked since the last withdrawal
+    pub fn withdraw_vested(ctx: Context<WithdrawVested>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let escrow = &mut ctx.accounts.vesting_escrow;
+
+        let vested = escrow.schedule.vested_amount(escrow.total_amount, now);
+        let amount = vested.saturating_sub(escrow.withdrawn);
+        require!(amount > 0, ErrorCode::NothingToWithdraw);
+        escrow.withdrawn = vested;
 
-        // Transfer tokens to user
-        let token_program = &ctx.accounts.token_program;
         let cpi_accounts = anchor_spl::token_interface::TransferChecked {
-            from: ctx.accounts.token_vault.to_account_info(),
+            from: ctx.accounts.escrow_vault.to_account_info(),
             mint: ctx.accounts.mint.to_account_info(),
-            to: ctx.accounts.user_token_account.to_account_info(),
-            authority: ctx.accounts.migration_authority.to_account_info(),
+            to: ctx.accounts.beneficiary_token_account.to_account_info(),
+            authority: escrow.to_account_info(),
         };
-
         let seeds = &[
-            b"migration",
-            migration.admin.as_ref(),
-            &migration.source_chain.to_le_bytes(),
-            &[migration.bump],
+            b"vesting",
+            escrow.migration.as_ref(),
+            escrow.beneficiary.as_ref(),
+            &[escrow.bump],
         ];
         let signer = &[&seeds[..]];
 
         anchor_spl::token_interface::transfer_checked(
-            CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer),
+            CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer),
             amount,
             ctx.accounts.mint.decimals,
         )?;
 
-        msg!("User {} claimed {} tokens", ctx.accounts.user.key(), amount);
+        msg!("User {} withdrew {} vested tokens", escrow.beneficiary, amount);
+        Ok(())
+    }
+
+    /// Claim tokens attested by a Wormhole VAA posted to the core bridge.
+    /// The emitter must be the source token registered on the migration, and
+    /// each VAA sequence can only be redeemed once.
+    pub fn claim_with_vaa(ctx: Context<ClaimWithVaa>, sequence: u64) -> Result<()> {
+        let migration = &mut ctx.accounts.migration;
+        migration.check_claimable(Clock::get()?.unix_timestamp)?;
+
+        let vaa = PostedVaa::parse(&ctx.accounts.posted_vaa.try_borrow_data()?)?;
+        require!(vaa.sequence == sequence, ErrorCode::InvalidVaa);
+        vaa.check_emitter(migration.source_chain, &migration.source_address)?;
+
+        let payload = ClaimPayload::parse(&vaa.payload)?;
+        require!(payload.amount > 0, ErrorCode::InvalidAmount);
+        require_keys_eq!(
+            payload.recipient,
+            ctx.accounts.recipient_token_account.owner,
+            ErrorCode::InvalidRecipient
+        );
+
+        // The attested amount is in source units, like snapshot leaves
+        let amount = migration.settle_claim(payload.amount)?;
+
+        // Record the sequence so the same VAA cannot be redeemed twice
+        let vaa_claim = &mut ctx.accounts.vaa_claim;
+        vaa_claim.migration = migration.key();
+        vaa_claim.sequence = sequence;
+        vaa_claim.recipient = payload.recipient;
+        vaa_claim.amount = amount;
+        vaa_claim.bump = ctx.bumps.vaa_claim;
+
+        let (net_amount, lp_amount) = pay_claim(
+            migration,
+            &ctx.accounts.token_program,
+            &ctx.accounts.token_vault,
+            &ctx.accounts.mint,
+            &ctx.accounts.recipient_token_account,
+            &ctx.accounts.migration_authority,
+            &ctx.accounts.dao_liquidity,
+            ctx.accounts.dao_vault.as_ref(),
+            amount,
+        )?;
+        ctx.accounts.vaa_claim.net_amount = net_amount;
+
+        msg!(
+            "VAA #{} claimed {} tokens for {} ({} to DAO LP reserve)",
+            sequence,
+            net_amount,
+            payload.recipient,
+            lp_amount
+        );
+        Ok(())
+    }
+
+    /// Allocate the claim-status bitmap chunk covering leaves
+    /// `[chunk_index * CLAIM_BITMAP_CHUNK_LEAVES, (chunk_index + 1) * CLAIM_BITMAP_CHUNK_LEAVES)`
+    pub fn initialize_claim_bitmap(ctx: Context<InitializeClaimBitmap>, chunk_index: u32) -> Result<()> {
+        let migration = &ctx.accounts.migration;
+        require!(migration.authority == ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+
+        let mut claim_bitmap = ctx.accounts.claim_bitmap.load_init()?;
+        claim_bitmap.migration = migration.key();
+        claim_bitmap.chunk_index = chunk_index;
+        claim_bitmap.claimed_count = 0;
+        claim_bitmap.bump = ctx.bumps.claim_bitmap;
+
+        msg!("Claim bitmap chunk {} initialized", chunk_index);
+        Ok(())
+    }
+
+    /// Same as `claim_tokens` for migrations created with
+    /// `ClaimTracking::Bitmap`: records the claim as a bit indexed by
+    /// `leaf_index` in a shared bitmap chunk instead of creating a UserClaim
+    /// PDA. Only covers the initial snapshot (epoch 0); later epochs are
+    /// claimed through `claim_tokens`.
+    pub fn claim_with_bitmap(
+        ctx: Context<ClaimWithBitmap>,
+        amount: u64,
+        merkle_proof: Vec<[u8; 32]>,
+        leaf_index: u32,
+    ) -> Result<()> {
+        let migration = &mut ctx.accounts.migration;
+        migration.check_claimable(Clock::get()?.unix_timestamp)?;
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        let hasher = SnapshotHasher::new(migration.hash_version, migration.key());
+        let leaf = hasher.leaf_hash(&ctx.accounts.user.key(), amount, leaf_index);
+        require!(
+            hasher.verify(leaf, &merkle_proof, &migration.snapshot_root),
+            ErrorCode::InvalidMerkleProof
+        );
+
+        let (_, bit) = ClaimBitmap::position(leaf_index);
+        ctx.accounts.claim_bitmap.load_mut()?.set_claimed(bit)?;
+        let amount = migration.settle_claim(amount)?;
+        let (net_amount, lp_amount) = pay_claim(
+            migration,
+            &ctx.accounts.token_program,
+            &ctx.accounts.token_vault,
+            &ctx.accounts.mint,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.migration_authority,
+            &ctx.accounts.dao_liquidity,
+            ctx.accounts.dao_vault.as_ref(),
+            amount,
+        )?;
+
+        // No per-claim account records the amounts in bitmap mode
+        emit!(BitmapClaimed {
+            migration: migration.key(),
+            user: ctx.accounts.user.key(),
+            leaf_index,
+            amount,
+            net_amount,
+        });
+        msg!("Leaf {} claimed {} tokens ({} to DAO LP reserve)", leaf_index, net_amount, lp_amount);
+        Ok(())
+    }
+
+    /// Return a bitmap chunk's rent to the admin once the migration is finalized
+    pub fn close_claim_bitmap(ctx: Context<CloseClaimBitmap>, chunk_index: u32) -> Result<()> {
+        let migration = &ctx.accounts.migration;
+        require!(migration.authority == ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+        require!(!migration.is_active, ErrorCode::MigrationStillActive);
+
+        msg!("Claim bitmap chunk {} closed", chunk_index);
+        Ok(())
+    }
+
+    /// Open a new snapshot epoch (e.g. LP holders, late stragglers) with its
+    /// own root, supply cap and claim window. Epochs are numbered from 1;
+    /// epoch 0 is the root passed to `initialize_migration`. Adds a claimable
+    /// root right away, so it's only available while no timelock is configured;
+    /// otherwise queue `OperationKind::AddSnapshotEpoch`.
+    /// `manifest_hash` commits to the manifest the root was built from, as for epoch 0.
+    pub fn add_snapshot_epoch(
+        ctx: Context<AddSnapshotEpoch>,
+        snapshot_root: [u8; 32],
+        manifest_hash: [u8; 32],
+        supply_cap: u64,
+        claim_start: i64,
+        claim_end: i64,
+    ) -> Result<()> {
+        let migration = &mut ctx.accounts.migration;
+        require!(migration.authority == ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+        open_snapshot_epoch(
+            migration,
+            &mut ctx.accounts.snapshot_epoch,
+            ctx.bumps.snapshot_epoch,
+            snapshot_root,
+            manifest_hash,
+            supply_cap,
+            claim_start,
+            claim_end,
+        )
+    }
+
+    /// Read-only view of the tokens still claimable under `total_supply`.
+    /// Intended to be simulated by dashboards; returns the value as return data.
+    pub fn remaining_supply(ctx: Context<MigrationView>) -> Result<u64> {
+        Ok(ctx.accounts.migration.remaining_supply())
+    }
+
+    /// Set the unix timestamp after which claims stop and leftovers can be swept.
+    /// The deadline can only move later, and not at all once it has passed.
+    /// Queue `OperationKind::SetClaimDeadline` while a timelock is configured.
+    pub fn set_claim_deadline(ctx: Context<UpdateMigration>, claim_deadline: i64) -> Result<()> {
+        let migration = &mut ctx.accounts.migration;
+        require!(migration.authority == ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+        require!(migration.timelock_delay == 0, ErrorCode::TimelockRequired);
+        require!(migration.is_active, ErrorCode::MigrationNotActive);
+        migration.extend_claim_deadline(claim_deadline, Clock::get()?.unix_timestamp)?;
+
+        msg!("Claim deadline set to {}", claim_deadline);
+        Ok(())
+    }
+
+    /// Permissionless: once the claim deadline has passed, move whatever is
+    /// left in the vault to the DAO treasury, or burn it if the migration has
+    /// no DAOLiquidity account
+    pub fn sweep_unclaimed(ctx: Context<SweepUnclaimed>) -> Result<()> {
+        let migration = &ctx.accounts.migration;
+        require!(migration.claim_deadline > 0, ErrorCode::ClaimDeadlineNotReached);
+        require!(
+            Clock::get()?.unix_timestamp >= migration.claim_deadline,
+            ErrorCode::ClaimDeadlineNotReached
+        );
+
+        let amount = ctx.accounts.token_vault.amount;
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        let migration_key = migration.key();
+        let seeds = &[b"authority", migration_key.as_ref(), &[migration.authority_bump]];
+        let signer = &[&seeds[..]];
+        let token_program = ctx.accounts.token_program.to_account_info();
+
+        // The DAOLiquidity PDA is always passed so the caller can't pick burn over the treasury
+        let dao_liquidity = &ctx.accounts.dao_liquidity;
+        let destination = if dao_liquidity.data_is_empty() {
+            let cpi_accounts = anchor_spl::token_interface::Burn {
+                mint: ctx.accounts.mint.to_account_info(),
+                from: ctx.accounts.token_vault.to_account_info(),
+                authority: ctx.accounts.migration_authority.to_account_info(),
+            };
+            anchor_spl::token_interface::burn(
+                CpiContext::new_with_signer(token_program, cpi_accounts, signer),
+                amount,
+            )?;
+            None
+        } else {
+            require_keys_eq!(*dao_liquidity.owner, crate::ID, ErrorCode::InvalidOperation);
+            let dao_liquidity = DAOLiquidity::try_deserialize(&mut &dao_liquidity.try_borrow_data()?[..])?;
+            let treasury = ctx
+                .accounts
+                .treasury_token_account
+                .as_ref()
+                .ok_or(ErrorCode::InvalidRecipient)?;
+            require_keys_eq!(treasury.owner, dao_liquidity.dao_treasury, ErrorCode::InvalidRecipient);
+
+            let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+                from: ctx.accounts.token_vault.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: treasury.to_account_info(),
+                authority: ctx.accounts.migration_authority.to_account_info(),
+            };
+            anchor_spl::token_interface::transfer_checked(
+                CpiContext::new_with_signer(token_program, cpi_accounts, signer),
+                amount,
+                ctx.accounts.mint.decimals,
+            )?;
+            Some(treasury.key())
+        };
+
+        emit!(UnclaimedSwept {
+            migration: migration.key(),
+            amount,
+            burned: destination.is_none(),
+            destination,
+        });
+        msg!("Swept {} unclaimed tokens", amount);
+        Ok(())
+    }
+
+    /// Assign the guardian allowed to pause claims and DAO contributions.
+    /// The guardian can veto queued operations, so replacing it is queued as
+    /// `OperationKind::SetGuardian` while a timelock is configured.
+    pub fn set_guardian(ctx: Context<UpdateMigration>, guardian: Pubkey) -> Result<()> {
+        let migration = &mut ctx.accounts.migration;
+        require!(migration.authority == ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+        require!(migration.timelock_delay == 0, ErrorCode::TimelockRequired);
+        migration.guardian = guardian;
+        msg!("Guardian set to {}", guardian);
+        Ok(())
+    }
+
+    /// Emergency stop, callable by the guardian or the admin
+    pub fn pause(ctx: Context<PauseMigration>) -> Result<()> {
+        let migration = &mut ctx.accounts.migration;
+        let authority = ctx.accounts.authority.key();
+        require!(
+            authority == migration.guardian || authority == migration.authority,
+            ErrorCode::Unauthorized
+        );
+        migration.paused = true;
+        msg!("Migration paused by {}", authority);
+        Ok(())
+    }
+
+    /// Only the admin can lift a pause
+    pub fn unpause(ctx: Context<UpdateMigration>) -> Result<()> {
+        let migration = &mut ctx.accounts.migration;
+        require!(migration.authority == ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+        migration.paused = false;
+        msg!("Migration unpaused");
+        Ok(())
+    }
+
+    /// First step of an admin handover; the new authority must accept it
+    pub fn propose_admin(ctx: Context<UpdateMigration>, new_authority: Pubkey) -> Result<()> {
+        ctx.accounts.migration.propose_authority(&ctx.accounts.admin.key(), new_authority)?;
+        msg!("Admin handover proposed to {}", new_authority);
+        Ok(())
+    }
+
+    /// Second step of an admin handover, signed by the proposed authority
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        ctx.accounts.migration.accept_authority(&ctx.accounts.new_authority.key())?;
+        msg!("Admin handed over to {}", ctx.accounts.new_authority.key());
         Ok(())
     }
 
+    /// Immediate finalize, only while no timelock is configured; otherwise
+    /// queue `OperationKind::Finalize`
     pub fn finalize_migration(ctx: Context<FinalizeMigration>) -> Result<()> {
         let migration = &mut ctx.accounts.migration;
-        require!(migration.admin == ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+        require!(migration.authority == ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+        require!(migration.timelock_delay == 0, ErrorCode::TimelockRequired);
         migration.is_active = false;
         msg!("Migration finalized");
         Ok(())
     }
 
-    /// Initialize a DAO-controlled liquidity pool to prevent "day 2 death"
-    /// Ensures liquidity stays with the community, not individual whales
-    pub fn initialize_dao_liquidity(
-        ctx: Context<InitializeDAOLiquidity>,
-        lp_reserve_percentage: u8,
+    /// Permissionless: once finalized no claim can mint again, so drop the
+    /// mint authority and fix the supply for good
+    pub fn revoke_mint_authority(ctx: Context<RevokeMintAuthority>) -> Result<()> {
+        let migration = &ctx.accounts.migration;
+        require!(!migration.is_active, ErrorCode::MigrationStillActive);
+
+        let migration_key = migration.key();
+        let seeds = &[b"authority", migration_key.as_ref(), &[migration.authority_bump]];
+        let signer = &[&seeds[..]];
+        let cpi_accounts = anchor_spl::token_interface::SetAuthority {
+            current_authority: ctx.accounts.migration_authority.to_account_info(),
+            account_or_mint: ctx.accounts.mint.to_account_info(),
+        };
+        anchor_spl::token_interface::set_authority(
+            CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer),
+            anchor_spl::token_interface::spl_token_2022::instruction::AuthorityType::MintTokens,
+            None,
+        )?;
+
+        msg!("Mint authority revoked for {}", ctx.accounts.mint.key());
+        Ok(())
+    }
+
+    /// Lengthen the timelock on admin operations, up to `MAX_TIMELOCK_DELAY`.
+    /// Shortening it has to be queued like any other sensitive change.
+    pub fn set_timelock_delay(ctx: Context<UpdateMigration>, delay: i64) -> Result<()> {
+        let migration = &mut ctx.accounts.migration;
+        require!(migration.authority == ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+        require!(delay >= migration.timelock_delay, ErrorCode::TimelockRequired);
+        require!(delay <= timelock::MAX_TIMELOCK_DELAY, ErrorCode::TimelockDelayTooLong);
+        migration.timelock_delay = delay;
+        msg!("Timelock delay set to {}s", delay);
+        Ok(())
+    }
+
+    /// Queue an admin operation to run no earlier than `timelock_delay` from now
+    pub fn queue_operation(ctx: Context<QueueOperation>, kind: OperationKind) -> Result<()> {
+        kind.validate()?;
+        let now = Clock::get()?.unix_timestamp;
+        let migration = &mut ctx.accounts.migration;
+        require!(migration.authority == ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+
+        record_queued_operation(migration, &mut ctx.accounts.queued_operation, ctx.bumps.queued_operation, kind, now)
+    }
+
+    /// Permissionless: apply a queued operation once its ETA has passed.
+    /// `AddSnapshotEpoch` also needs `payer`, `snapshot_epoch` and `system_program`.
+    pub fn execute_operation(ctx: Context<ExecuteOperation>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let operation = &mut ctx.accounts.queued_operation;
+        operation.check_executable(now)?;
+
+        let migration = &mut ctx.accounts.migration;
+        match operation.kind {
+            OperationKind::AddSnapshotEpoch { snapshot_root, manifest_hash, supply_cap, claim_start, claim_end } => {
+                open_snapshot_epoch(
+                    migration,
+                    ctx.accounts.snapshot_epoch.as_mut().ok_or(ErrorCode::InvalidOperation)?,
+                    ctx.bumps.snapshot_epoch.ok_or(ErrorCode::InvalidOperation)?,
+                    snapshot_root,
+                    manifest_hash,
+                    supply_cap,
+                    claim_start,
+                    claim_end,
+                )?;
+            }
+            kind => kind.apply(migration, ctx.accounts.dao_liquidity.as_deref_mut(), now)?,
+        }
+        operation.status = OperationStatus::Executed;
+        msg!("Operation {} executed: {:?}", operation.id, operation.kind);
+        Ok(())
+    }
+
+    /// Admin withdraws an operation it queued
+    pub fn cancel_operation(ctx: Context<ResolveOperation>) -> Result<()> {
+        require!(
+            ctx.accounts.migration.authority == ctx.accounts.authority.key(),
+            ErrorCode::Unauthorized
+        );
+        let operation = &mut ctx.accounts.queued_operation;
+        operation.check_pending()?;
+        operation.status = OperationStatus::Cancelled;
+        msg!("Operation {} cancelled", operation.id);
+        Ok(())
+    }
+
+    /// Guardian blocks a queued operation before it executes
+    pub fn veto_operation(ctx: Context<ResolveOperation>) -> Result<()> {
+        require!(
+            ctx.accounts.migration.guardian == ctx.accounts.authority.key(),
+            ErrorCode::Unauthorized
+        );
+        let operation = &mut ctx.accounts.queued_operation;
+        operation.check_pending()?;
+        operation.status = OperationStatus::Vetoed;
+        msg!("Operation {} vetoed by guardian", operation.id);
+        Ok(())
+    }
+
+    /// Permissionless: block a queued operation with a passed proposal whose
+    /// actions include `VetoOperation` for it. The proposal's execution delay
+    /// doesn't apply, since a veto only ever stops a change.
+    pub fn veto_operation_by_vote(ctx: Context<VetoOperationByVote>) -> Result<()> {
+        let operation_key = ctx.accounts.queued_operation.key();
+        let proposal = &mut ctx.accounts.proposal;
+        require!(proposal.status == ProposalStatus::Passed, ErrorCode::ProposalNotPassed);
+        require!(
+            proposal.actions.contains(&ProposalAction::VetoOperation { operation: operation_key }),
+            ErrorCode::InvalidOperation
+        );
+
+        let operation = &mut ctx.accounts.queued_operation;
+        operation.check_pending()?;
+        operation.status = OperationStatus::Vetoed;
+        proposal.status = ProposalStatus::Executed;
+        msg!("Operation {} vetoed by proposal {}", operation.id, proposal.id);
+        Ok(())
+    }
+
+    /// Set up quadratic-vote governance for a migration's token. Proposals
+    /// only pass with at least `quorum_voters` distinct wallets and
+    /// `quorum_weight` total quadratic weight, and their actions can run
+    /// `execution_delay` seconds after voting ends.
+    pub fn initialize_governance(
+        ctx: Context<InitializeGovernance>,
+        voting_period: i64,
+        quorum_voters: u32,
+        quorum_weight: u64,
+        execution_delay: i64,
     ) -> Result<()> {
-        require!(lp_reserve_percentage > 0 && lp_reserve_percentage <= 20, ErrorCode::InvalidOperation);
-        
-        let dao_liq = &mut ctx.accounts.dao_liquidity;
-        dao_liq.migration = ctx.accounts.migration.key();
-        dao_liq.dao_treasury = ctx.accounts.admin.key();
-        dao_liq.lp_percentage = lp_reserve_percentage;
-        dao_liq.pool_initialized = false;
-        dao_liq.total_reserved = 0;
-        dao_liq.bump = ctx.bumps.dao_liquidity;
-        
-        msg!("DAO liquidity pool initialized with {}% reserve requirement", lp_reserve_percentage);
+        let migration = &ctx.accounts.migration;
+        require!(migration.authority == ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+        require!(voting_period > 0, ErrorCode::InvalidOperation);
+        require!(execution_delay >= 0, ErrorCode::InvalidOperation);
+
+        let governance = &mut ctx.accounts.governance;
+        governance.migration = migration.key();
+        governance.total_votes = 0;
+        governance.mint = ctx.accounts.mint.key();
+        governance.proposal_count = 0;
+        governance.voting_period = voting_period;
+        governance.bump = ctx.bumps.governance;
+        governance.quorum_voters = quorum_voters;
+        governance.quorum_weight = quorum_weight;
+        governance.execution_delay = execution_delay;
+
+        msg!("Governance initialized with {}s voting period", voting_period);
         Ok(())
     }
 
-    /// Contribute tokens to the DAO-controlled liquidity pool
-    pub fn contribute_to_dao_lp(
-        ctx: Context<ContributeDAOLiquidity>,
-        amount: u64,
+    /// Change the quorum for proposals created from now on
+    pub fn set_quorum(ctx: Context<UpdateGovernance>, quorum_voters: u32, quorum_weight: u64) -> Result<()> {
+        require!(
+            ctx.accounts.migration.authority == ctx.accounts.admin.key(),
+            ErrorCode::Unauthorized
+        );
+        let governance = &mut ctx.accounts.governance;
+        governance.quorum_voters = quorum_voters;
+        governance.quorum_weight = quorum_weight;
+        msg!("Quorum set to {} wallets / {} weight", quorum_voters, quorum_weight);
+        Ok(())
+    }
+
+    /// Hand the migration admin to its governance PDA. Permissionless, but
+    /// only completes a handover the current admin proposed with `propose_admin`.
+    pub fn accept_governance_admin(ctx: Context<AcceptGovernanceAdmin>) -> Result<()> {
+        let governance = ctx.accounts.governance.key();
+        ctx.accounts.migration.accept_authority(&governance)?;
+        msg!("Admin handed over to governance {}", governance);
+        Ok(())
+    }
+
+    /// Open a proposal for `governance.voting_period` seconds. The proposal text
+    /// lives off-chain; `description_hash` commits to it. `actions` run through
+    /// `execute_proposal` if the proposal passes; a `VetoOperation` must be the
+    /// only action and runs through `veto_operation_by_vote` instead.
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        description_hash: [u8; 32],
+        actions: Vec<ProposalAction>,
     ) -> Result<()> {
+        governance::validate_actions(&actions)?;
+        let now = Clock::get()?.unix_timestamp;
+        let governance = &mut ctx.accounts.governance;
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.governance = governance.key();
+        proposal.id = governance.proposal_count;
+        proposal.proposer = ctx.accounts.proposer.key();
+        proposal.description_hash = description_hash;
+        proposal.votes_for = 0;
+        proposal.votes_against = 0;
+        proposal.voting_starts = now;
+        proposal.voting_ends = now.checked_add(governance.voting_period).ok_or(ErrorCode::InvalidOperation)?;
+        proposal.status = ProposalStatus::Active;
+        proposal.bump = ctx.bumps.proposal;
+        proposal.unique_voters = 0;
+        proposal.quorum_voters = governance.quorum_voters;
+        proposal.quorum_weight = governance.quorum_weight;
+        proposal.executable_at = proposal.voting_ends
+            .checked_add(governance.execution_delay)
+            .ok_or(ErrorCode::InvalidOperation)?;
+        proposal.actions = actions;
+
+        governance.proposal_count += 1;
+
+        msg!("Proposal {} created", proposal.id);
+        Ok(())
+    }
+
+    /// Vote with weight isqrt(tokens locked in the voter's escrow). Only locks
+    /// that predate the proposal count, and the VoteRecord PDA makes a second
+    /// vote from the same wallet fail.
+    pub fn cast_vote(ctx: Context<CastVote>, support: bool) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        let now = Clock::get()?.unix_timestamp;
+        require!(proposal.status == ProposalStatus::Active, ErrorCode::ProposalNotActive);
+        require!(now < proposal.voting_ends, ErrorCode::VotingClosed);
+
+        let stake = ctx.accounts.vote_escrow.voting_power_at(proposal.voting_starts);
+        require!(stake > 0, ErrorCode::NoVotingPower);
+        let weight = governance::vote_weight(stake);
+        require!(weight > 0, ErrorCode::InvalidAmount);
+
+        if support {
+            proposal.votes_for = proposal.votes_for.checked_add(weight).ok_or(ErrorCode::InvalidOperation)?;
+        } else {
+            proposal.votes_against = proposal.votes_against.checked_add(weight).ok_or(ErrorCode::InvalidOperation)?;
+        }
+        proposal.unique_voters = proposal.unique_voters.checked_add(1).ok_or(ErrorCode::InvalidOperation)?;
+
+        let governance = &mut ctx.accounts.governance;
+        governance.total_votes = governance.total_votes.checked_add(weight).ok_or(ErrorCode::InvalidOperation)?;
+
+        let vote_record = &mut ctx.accounts.vote_record;
+        vote_record.proposal = proposal.key();
+        vote_record.voter = ctx.accounts.voter.key();
+        vote_record.stake = stake;
+        vote_record.weight = weight;
+        vote_record.support = support;
+        vote_record.bump = ctx.bumps.vote_record;
+
+        msg!("Voter {} cast {} weight {}", ctx.accounts.voter.key(), if support { "for" } else { "against" }, weight);
+        Ok(())
+    }
+
+    /// Create the escrow that holds a wallet's governance tokens
+    pub fn open_vote_escrow(ctx: Context<OpenVoteEscrow>) -> Result<()> {
+        let vote_escrow = &mut ctx.accounts.vote_escrow;
+        vote_escrow.governance = ctx.accounts.governance.key();
+        vote_escrow.owner = ctx.accounts.owner.key();
+        vote_escrow.amount = 0;
+        vote_escrow.locked_at = 0;
+        vote_escrow.bump = ctx.bumps.vote_escrow;
+        vote_escrow.prior_amount = 0;
+        vote_escrow.prior_locked_at = 0;
+        Ok(())
+    }
+
+    /// Lock tokens for voting. Tokens moved in after a proposal was created
+    /// can't vote on it; the balance locked before the top-up still can.
+    pub fn lock_tokens(ctx: Context<LockTokens>, amount: u64) -> Result<()> {
         require!(amount > 0, ErrorCode::InvalidAmount);
-        
-        let dao_liq = &mut ctx.accounts.dao_liquidity;
-        require!(dao_liq.migration == ctx.accounts.migration.key(), ErrorCode::InvalidOperation);
-        
-        // Transfer tokens to DAO treasury
-        let token_program = &ctx.accounts.token_program;
+
         let cpi_accounts = anchor_spl::token_interface::TransferChecked {
-            from: ctx.accounts.user_token_account.to_account_info(),
+            from: ctx.accounts.owner_token_account.to_account_info(),
             mint: ctx.accounts.mint.to_account_info(),
-            to: ctx.accounts.dao_vault.to_account_info(),
-            authority: ctx.accounts.user.to_account_info(),
+            to: ctx.accounts.escrow_vault.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
         };
-
         anchor_spl::token_interface::transfer_checked(
-            CpiContext::new(token_program.to_account_info(), cpi_accounts),
+            CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
             amount,
             ctx.accounts.mint.decimals,
         )?;
 
-        dao_liq.total_reserved = dao_liq.total_reserved.checked_add(amount)
-            .ok_or(ErrorCode::InvalidOperation)?;
+        ctx.accounts.vote_escrow.lock(amount, Clock::get()?.unix_timestamp)?;
+        msg!("Locked {} tokens for voting", amount);
+        Ok(())
+    }
+
+    /// Withdraw locked tokens. Votes already cast keep their weight, but the
+    /// tokens can't vote again on those proposals from another escrow.
+    pub fn unlock_tokens(ctx: Context<UnlockTokens>, amount: u64) -> Result<()> {
+        let vote_escrow = &mut ctx.accounts.vote_escrow;
+        vote_escrow.unlock(amount)?;
+
+        let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+            from: ctx.accounts.escrow_vault.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.owner_token_account.to_account_info(),
+            authority: vote_escrow.to_account_info(),
+        };
+        let seeds = &[
+            b"vote_escrow",
+            vote_escrow.governance.as_ref(),
+            vote_escrow.owner.as_ref(),
+            &[vote_escrow.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        anchor_spl::token_interface::transfer_checked(
+            CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+
+        msg!("Unlocked {} tokens", amount);
+        Ok(())
+    }
+
+    /// Permissionless: settle a proposal once voting has ended
+    pub fn tally(ctx: Context<Tally>) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        let now = Clock::get()?.unix_timestamp;
+        require!(proposal.status == ProposalStatus::Active, ErrorCode::ProposalNotActive);
+        require!(now >= proposal.voting_ends, ErrorCode::VotingStillOpen);
+
+        if let Err(reason) = proposal.check_quorum() {
+            msg!("Proposal {} below quorum: {}", proposal.id, reason);
+        }
+        proposal.status = proposal.outcome();
+        msg!("Proposal {} tallied: {:?}", proposal.id, proposal.status);
+        Ok(())
+    }
+
+    /// Permissionless: run a passed proposal's actions once its timelock has
+    /// expired, acting as the governance PDA that holds the migration admin.
+    /// `AddSnapshotEpoch` and `QueueOperation` actions also need `payer`, the
+    /// account they create and `system_program`.
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let governance = ctx.accounts.governance.key();
+        let migration = &mut ctx.accounts.migration;
+        require!(migration.authority == governance, ErrorCode::Unauthorized);
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.check_executable(now)?;
+        // Governance gets no shorter a wait than the admin for timelocked changes
+        if proposal.actions.iter().any(ProposalAction::is_timelocked) {
+            require!(
+                ctx.accounts.governance.execution_delay >= migration.timelock_delay,
+                ErrorCode::TimelockRequired
+            );
+        }
+
+        let mut dao_liquidity = ctx.accounts.dao_liquidity.as_deref_mut();
+        for action in proposal.actions.iter() {
+            match *action {
+                ProposalAction::AddSnapshotEpoch { snapshot_root, manifest_hash, supply_cap, claim_start, claim_end } => {
+                    open_snapshot_epoch(
+                        migration,
+                        ctx.accounts.snapshot_epoch.as_mut().ok_or(ErrorCode::InvalidOperation)?,
+                        ctx.bumps.snapshot_epoch.ok_or(ErrorCode::InvalidOperation)?,
+                        snapshot_root,
+                        manifest_hash,
+                        supply_cap,
+                        claim_start,
+                        claim_end,
+                    )?;
+                }
+                ProposalAction::QueueOperation { kind } => {
+                    record_queued_operation(
+                        migration,
+                        ctx.accounts.queued_operation.as_mut().ok_or(ErrorCode::InvalidOperation)?,
+                        ctx.bumps.queued_operation.ok_or(ErrorCode::InvalidOperation)?,
+                        kind,
+                        now,
+                    )?;
+                }
+                _ => action.apply(migration, dao_liquidity.as_deref_mut())?,
+            }
+            msg!("Executed {:?}", action);
+        }
+
+        proposal.status = ProposalStatus::Executed;
+        msg!("Proposal {} executed", proposal.id);
+        Ok(())
+    }
+
+    /// Initialize a DAO-controlled liquidity pool to prevent "day 2 death"
+    /// Ensures liquidity stays with the community, not individual whales
+    pub fn initialize_dao_liquidity(
+        ctx: Context<InitializeDAOLiquidity>,
+        lp_reserve_percentage: u8,
+    ) -> Result<()> {
+        require!(lp_reserve_percentage > 0 && lp_reserve_percentage <= 20, ErrorCode::InvalidOperation);
+        
+        let dao_liq = &mut ctx.accounts.dao_liquidity;
+        dao_liq.migration = ctx.accounts.migration.key();
+        dao_liq.dao_treasury = ctx.accounts.admin.key();
+        dao_liq.lp_percentage = lp_reserve_percentage;
+        dao_liq.pool_initialized = false;
+        dao_liq.total_reserved = 0;
+        dao_liq.bump = ctx.bumps.dao_liquidity;
+        dao_liq.pending_treasury = Pubkey::default();
+        dao_liq.amm_program = Pubkey::default();
+        dao_liq.lp_mint = Pubkey::default();
+        
+        msg!("DAO liquidity pool initialized with {}% reserve requirement", lp_reserve_percentage);
+        Ok(())
+    }
+
+    /// Propose a new DAO treasury; the current treasury signs, the new one
+    /// accepts. Once a timelock is configured, queue `OperationKind::SetDaoTreasury`
+    /// instead.
+    pub fn propose_dao_treasury(ctx: Context<ProposeDAOTreasury>, new_treasury: Pubkey) -> Result<()> {
+        let dao_liq = &mut ctx.accounts.dao_liquidity;
+        require!(dao_liq.dao_treasury == ctx.accounts.dao_treasury.key(), ErrorCode::Unauthorized);
+        dao_liq.pending_treasury = new_treasury;
+        msg!("DAO treasury handover proposed to {}", new_treasury);
+        Ok(())
+    }
+
+    pub fn accept_dao_treasury(ctx: Context<AcceptDAOTreasury>) -> Result<()> {
+        let dao_liq = &mut ctx.accounts.dao_liquidity;
+        let new_treasury = ctx.accounts.new_treasury.key();
+        require!(
+            dao_liq.pending_treasury != Pubkey::default() && dao_liq.pending_treasury == new_treasury,
+            ErrorCode::Unauthorized
+        );
+        dao_liq.dao_treasury = new_treasury;
+        dao_liq.pending_treasury = Pubkey::default();
+        msg!("DAO treasury handed over to {}", new_treasury);
+        Ok(())
+    }
+
+    /// Contribute tokens to the DAO-controlled liquidity pool
+    pub fn contribute_to_dao_lp(
+        ctx: Context<ContributeDAOLiquidity>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(!ctx.accounts.migration.paused, ErrorCode::MigrationPaused);
+        
+        let dao_liq = &mut ctx.accounts.dao_liquidity;
+        
+        // Transfer tokens to DAO treasury
+        let token_program = &ctx.accounts.token_program;
+        let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.dao_vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+
+        anchor_spl::token_interface::transfer_checked(
+            CpiContext::new(token_program.to_account_info(), cpi_accounts),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+
+        dao_liq.total_reserved = dao_liq.total_reserved.checked_add(amount)
+            .ok_or(ErrorCode::InvalidOperation)?;
+
+        msg!("User {} contributed {} tokens to DAO LP pool", ctx.accounts.user.key(), amount);
+        Ok(())
+    }
+
+    /// Once `total_reserved` reaches `lp_percentage` of the total supply, pair
+    /// the reserve with `quote_amount` from the treasury in an AMM pool and lock
+    /// the LP tokens in a DAOLiquidity-owned account. AMM-specific accounts are
+    /// passed as remaining accounts, in the order the adapter expects. Mainnet
+    /// builds register no adapter yet, so this fails there (see `amm`).
+    pub fn seed_dao_pool<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SeedDAOPool<'info>>,
+        quote_amount: u64,
+        min_lp: u64,
+    ) -> Result<()> {
+        require!(quote_amount > 0, ErrorCode::InvalidAmount);
+        require!(!ctx.accounts.migration.paused, ErrorCode::MigrationPaused);
+
+        let dao_liq = &ctx.accounts.dao_liquidity;
+        require!(!dao_liq.pool_initialized, ErrorCode::PoolAlreadyInitialized);
+        require!(
+            dao_liq.reserve_reached(ctx.accounts.migration.total_supply),
+            ErrorCode::LpReserveNotReached
+        );
+        let token_amount = dao_liq.total_reserved;
+        let adapter = amm::adapter_for(&ctx.accounts.amm_program.key())?;
+
+        // Move the quote side under the DAOLiquidity PDA so it can sign for both
+        let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+            from: ctx.accounts.treasury_quote_account.to_account_info(),
+            mint: ctx.accounts.quote_mint.to_account_info(),
+            to: ctx.accounts.quote_vault.to_account_info(),
+            authority: ctx.accounts.dao_treasury.to_account_info(),
+        };
+        anchor_spl::token_interface::transfer_checked(
+            CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+            quote_amount,
+            ctx.accounts.quote_mint.decimals,
+        )?;
+
+        let depositor = ctx.accounts.dao_liquidity.to_account_info();
+        let token_mint = ctx.accounts.mint.to_account_info();
+        let quote_mint = ctx.accounts.quote_mint.to_account_info();
+        let token_source = ctx.accounts.dao_vault.to_account_info();
+        let quote_source = ctx.accounts.quote_vault.to_account_info();
+        let lp_mint = ctx.accounts.lp_mint.to_account_info();
+        let lp_destination = ctx.accounts.lp_lock.to_account_info();
+        let token_program = ctx.accounts.token_program.to_account_info();
+        let deposit = amm::DepositAccounts {
+            depositor: &depositor,
+            token_mint: &token_mint,
+            quote_mint: &quote_mint,
+            token_source: &token_source,
+            quote_source: &quote_source,
+            lp_mint: &lp_mint,
+            lp_destination: &lp_destination,
+            token_program: &token_program,
+            pool_accounts: ctx.remaining_accounts,
+        };
+        let ix = adapter.deposit_instruction(&deposit, token_amount, quote_amount, min_lp)?;
+
+        let mut infos = vec![
+            depositor.clone(),
+            token_mint.clone(),
+            quote_mint.clone(),
+            token_source.clone(),
+            quote_source.clone(),
+            lp_mint.clone(),
+            lp_destination.clone(),
+            token_program.clone(),
+            ctx.accounts.amm_program.to_account_info(),
+        ];
+        infos.extend_from_slice(ctx.remaining_accounts);
+
+        let migration_key = ctx.accounts.migration.key();
+        let seeds = &[b"dao_liquidity", migration_key.as_ref(), &[ctx.accounts.dao_liquidity.bump]];
+        let signer = &[&seeds[..]];
+        anchor_lang::solana_program::program::invoke_signed(&ix, &infos, signer)?;
+
+        let dao_liq = &mut ctx.accounts.dao_liquidity;
+        dao_liq.pool_initialized = true;
+        dao_liq.amm_program = adapter.program_id();
+        dao_liq.lp_mint = ctx.accounts.lp_mint.key();
+
+        msg!("DAO pool seeded with {} tokens and {} quote", token_amount, quote_amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(name: [u8; 64], source_chain: u16, source_address: [u8; 32], snapshot_root: [u8; 32], total_supply: u64, supply_mode: SupplyMode, decimals: u8)]
+pub struct InitializeMigration<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + size_of::<Migration>(),
+        seeds = [b"migration", admin.key().as_ref(), &source_chain.to_le_bytes()],
+        bump
+    )]
+    pub migration: Account<'info, Migration>,
+    
+    #[account(
+        init,
+        payer = admin,
+        mint::decimals = decimals,
+        mint::authority = migration_authority,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    /// CHECK: The authority for the migration account
+    #[account(
+        seeds = [b"authority", migration.key().as_ref()],
+        bump
+    )]
+    pub migration_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        token::mint = mint,
+        token::authority = migration_authority,
+        token::token_program = token_program,
+        seeds = [b"vault", migration.key().as_ref()],
+        bump
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, merkle_proof: Vec<[u8; 32]>, leaf_index: u32, epoch: u32)]
+pub struct ClaimTokens<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        mut,
+        has_one = mint,
+        has_one = token_vault,
+        constraint = epoch > 0 || migration.claim_tracking == ClaimTracking::UserClaim @ ErrorCode::ClaimTrackingMismatch
+    )]
+    pub migration: Account<'info, Migration>,
+    
+    #[account(
+        init,
+        payer = user,
+        space = 8 + size_of::<UserClaim>(),
+        seeds = [
+            b"claim",
+            migration.key().as_ref(),
+            user.key().as_ref(),
+            UserClaim::epoch_seed(&epoch.to_le_bytes())
+        ],
+        bump
+    )]
+    pub user_claim: Account<'info, UserClaim>,
+
+    /// Required when claiming against epoch > 0
+    #[account(
+        mut,
+        seeds = [b"epoch", migration.key().as_ref(), &epoch.to_le_bytes()],
+        bump = snapshot_epoch.bump
+    )]
+    pub snapshot_epoch: Option<Account<'info, SnapshotEpoch>>,
+    
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(mut)]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = mint, token::authority = user)]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: DAOLiquidity PDA for this migration; empty means no LP carve-out
+    #[account(mut, seeds = [b"dao_liquidity", migration.key().as_ref()], bump)]
+    pub dao_liquidity: UncheckedAccount<'info>,
+
+    /// Required when a DAOLiquidity account exists
+    #[account(mut)]
+    pub dao_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// CHECK: PDA that owns the vault and the mint authority
+    #[account(seeds = [b"authority", migration.key().as_ref()], bump = migration.authority_bump)]
+    pub migration_authority: UncheckedAccount<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimTokensRelayed<'info> {
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    /// CHECK: Wallet from the Merkle leaf, authorizes via the Ed25519 instruction
+    pub beneficiary: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = mint,
+        has_one = token_vault,
+        constraint = migration.claim_tracking == ClaimTracking::UserClaim @ ErrorCode::ClaimTrackingMismatch
+    )]
+    pub migration: Account<'info, Migration>,
+
+    #[account(
+        init,
+        payer = relayer,
+        space = 8 + size_of::<UserClaim>(),
+        seeds = [b"claim", migration.key().as_ref(), beneficiary.key().as_ref()],
+        bump
+    )]
+    pub user_claim: Account<'info, UserClaim>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, token::mint = mint, token::authority = beneficiary)]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: DAOLiquidity PDA for this migration; empty means no LP carve-out
+    #[account(mut, seeds = [b"dao_liquidity", migration.key().as_ref()], bump)]
+    pub dao_liquidity: UncheckedAccount<'info>,
+
+    /// Required when a DAOLiquidity account exists
+    #[account(mut)]
+    pub dao_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: PDA that owns the vault and the mint authority
+    #[account(seeds = [b"authority", migration.key().as_ref()], bump = migration.authority_bump)]
+    pub migration_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar, used to read the Ed25519 instruction
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(evm_address: [u8; 20])]
+pub struct ClaimTokensEvm<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Solana wallet bound to the EVM address by the secp256k1 signature
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(mut, has_one = mint, has_one = token_vault)]
+    pub migration: Account<'info, Migration>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + size_of::<UserClaim>(),
+        seeds = [b"claim", migration.key().as_ref(), evm_address.as_ref()],
+        bump
+    )]
+    pub user_claim: Account<'info, UserClaim>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, token::mint = mint, token::authority = recipient)]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: DAOLiquidity PDA for this migration; empty means no LP carve-out
+    #[account(mut, seeds = [b"dao_liquidity", migration.key().as_ref()], bump)]
+    pub dao_liquidity: UncheckedAccount<'info>,
+
+    /// Required when a DAOLiquidity account exists
+    #[account(mut)]
+    pub dao_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: PDA that owns the vault and the mint authority
+    #[account(seeds = [b"authority", migration.key().as_ref()], bump = migration.authority_bump)]
+    pub migration_authority: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVesting<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut, has_one = mint, has_one = token_vault)]
+    pub migration: Account<'info, Migration>,
+
+    #[account(
+        init,
+        payer = user,
+        space = 8 + size_of::<VestingEscrow>(),
+        seeds = [b"vesting", migration.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub vesting_escrow: Account<'info, VestingEscrow>,
+
+    #[account(
+        init,
+        payer = user,
+        token::mint = mint,
+        token::authority = vesting_escrow,
+        token::token_program = token_program,
+        seeds = [b"vesting_vault", vesting_escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: DAOLiquidity PDA for this migration; empty means no LP carve-out
+    #[account(mut, seeds = [b"dao_liquidity", migration.key().as_ref()], bump)]
+    pub dao_liquidity: UncheckedAccount<'info>,
+
+    /// Required when a DAOLiquidity account exists
+    #[account(mut)]
+    pub dao_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: PDA that owns the vault and the mint authority
+    #[account(seeds = [b"authority", migration.key().as_ref()], bump = migration.authority_bump)]
+    pub migration_authority: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawVested<'info> {
+    pub beneficiary: Signer<'info>,
+
+    #[account(mut, has_one = beneficiary)]
+    pub vesting_escrow: Account<'info, VestingEscrow>,
+
+    #[account(
+        mut,
+        seeds = [b"vesting_vault", vesting_escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub beneficiary_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(sequence: u64)]
+pub struct ClaimWithVaa<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut, has_one = mint, has_one = token_vault)]
+    pub migration: Account<'info, Migration>,
+
+    /// CHECK: Owner is the core bridge, layout and emitter verified in the handler
+    #[account(owner = CORE_BRIDGE_PROGRAM_ID)]
+    pub posted_vaa: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + size_of::<VaaClaim>(),
+        seeds = [b"vaa_claim", migration.key().as_ref(), &sequence.to_le_bytes()],
+        bump
+    )]
+    pub vaa_claim: Account<'info, VaaClaim>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Owner is checked against the VAA payload's recipient in the handler
+    #[account(mut, token::mint = mint)]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: DAOLiquidity PDA for this migration; empty means no LP carve-out
+    #[account(mut, seeds = [b"dao_liquidity", migration.key().as_ref()], bump)]
+    pub dao_liquidity: UncheckedAccount<'info>,
+
+    /// Required when a DAOLiquidity account exists
+    #[account(mut)]
+    pub dao_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: PDA that owns the vault and the mint authority
+    #[account(seeds = [b"authority", migration.key().as_ref()], bump = migration.authority_bump)]
+    pub migration_authority: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeMigration<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(mut)]
+    pub migration: Account<'info, Migration>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeMintAuthority<'info> {
+    #[account(has_one = mint)]
+    pub migration: Account<'info, Migration>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: PDA that owns the vault and the mint authority
+    #[account(seeds = [b"authority", migration.key().as_ref()], bump = migration.authority_bump)]
+    pub migration_authority: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct QueueOperation<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub migration: Account<'info, Migration>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + size_of::<QueuedOperation>(),
+        seeds = [b"queued_op", migration.key().as_ref(), &migration.operation_count.to_le_bytes()],
+        bump
+    )]
+    pub queued_operation: Account<'info, QueuedOperation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteOperation<'info> {
+    #[account(mut)]
+    pub migration: Account<'info, Migration>,
+
+    #[account(mut, has_one = migration)]
+    pub queued_operation: Account<'info, QueuedOperation>,
+
+    /// Required by treasury operations
+    #[account(
+        mut,
+        seeds = [b"dao_liquidity", migration.key().as_ref()],
+        bump = dao_liquidity.bump
+    )]
+    pub dao_liquidity: Option<Account<'info, DAOLiquidity>>,
+
+    /// Pays for the epoch created by `AddSnapshotEpoch`
+    #[account(mut)]
+    pub payer: Option<Signer<'info>>,
+
+    /// Required by `AddSnapshotEpoch`
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + size_of::<SnapshotEpoch>(),
+        seeds = [b"epoch", migration.key().as_ref(), &(migration.epoch_count + 1).to_le_bytes()],
+        bump
+    )]
+    pub snapshot_epoch: Option<Account<'info, SnapshotEpoch>>,
+
+    pub system_program: Option<Program<'info, System>>,
+}
+
+/// Cancel (admin) or veto (guardian) a queued operation
+#[derive(Accounts)]
+pub struct ResolveOperation<'info> {
+    pub authority: Signer<'info>,
+
+    pub migration: Account<'info, Migration>,
+
+    #[account(mut, has_one = migration)]
+    pub queued_operation: Account<'info, QueuedOperation>,
+}
+
+#[derive(Accounts)]
+pub struct VetoOperationByVote<'info> {
+    pub migration: Account<'info, Migration>,
+
+    #[account(
+        seeds = [b"governance", migration.key().as_ref()],
+        bump = governance.bump,
+        has_one = migration
+    )]
+    pub governance: Account<'info, Governance>,
+
+    #[account(mut, has_one = governance)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(mut, has_one = migration)]
+    pub queued_operation: Account<'info, QueuedOperation>,
+}
+
+#[derive(Accounts)]
+#[instruction(chunk_index: u32)]
+pub struct InitializeClaimBitmap<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(constraint = migration.claim_tracking == ClaimTracking::Bitmap @ ErrorCode::ClaimTrackingMismatch)]
+    pub migration: Account<'info, Migration>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + size_of::<ClaimBitmap>(),
+        seeds = [b"claim_bitmap", migration.key().as_ref(), &chunk_index.to_le_bytes()],
+        bump
+    )]
+    pub claim_bitmap: AccountLoader<'info, ClaimBitmap>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, merkle_proof: Vec<[u8; 32]>, leaf_index: u32)]
+pub struct ClaimWithBitmap<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = mint,
+        has_one = token_vault,
+        constraint = migration.claim_tracking == ClaimTracking::Bitmap @ ErrorCode::ClaimTrackingMismatch
+    )]
+    pub migration: Account<'info, Migration>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"claim_bitmap",
+            migration.key().as_ref(),
+            &(leaf_index / CLAIM_BITMAP_CHUNK_LEAVES).to_le_bytes()
+        ],
+        bump = claim_bitmap.load()?.bump
+    )]
+    pub claim_bitmap: AccountLoader<'info, ClaimBitmap>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, token::mint = mint, token::authority = user)]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: DAOLiquidity PDA for this migration; empty means no LP carve-out
+    #[account(mut, seeds = [b"dao_liquidity", migration.key().as_ref()], bump)]
+    pub dao_liquidity: UncheckedAccount<'info>,
+
+    /// Required when a DAOLiquidity account exists
+    #[account(mut)]
+    pub dao_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: PDA that owns the vault and the mint authority
+    #[account(seeds = [b"authority", migration.key().as_ref()], bump = migration.authority_bump)]
+    pub migration_authority: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(chunk_index: u32)]
+pub struct CloseClaimBitmap<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub migration: Account<'info, Migration>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"claim_bitmap", migration.key().as_ref(), &chunk_index.to_le_bytes()],
+        bump = claim_bitmap.load()?.bump
+    )]
+    pub claim_bitmap: AccountLoader<'info, ClaimBitmap>,
+}
+
+#[derive(Accounts)]
+pub struct AddSnapshotEpoch<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(mut, constraint = migration.timelock_delay == 0 @ ErrorCode::TimelockRequired)]
+    pub migration: Account<'info, Migration>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + size_of::<SnapshotEpoch>(),
+        seeds = [b"epoch", migration.key().as_ref(), &(migration.epoch_count + 1).to_le_bytes()],
+        bump
+    )]
+    pub snapshot_epoch: Account<'info, SnapshotEpoch>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrationView<'info> {
+    pub migration: Account<'info, Migration>,
+}
+
+/// Admin-only parameter updates on an existing migration
+#[derive(Accounts)]
+pub struct UpdateMigration<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub migration: Account<'info, Migration>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub new_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub migration: Account<'info, Migration>,
+}
+
+#[derive(Accounts)]
+pub struct PauseMigration<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub migration: Account<'info, Migration>,
+}
+
+#[derive(Accounts)]
+pub struct SweepUnclaimed<'info> {
+    #[account(has_one = mint, has_one = token_vault)]
+    pub migration: Account<'info, Migration>,
+
+    /// CHECK: DAOLiquidity PDA for this migration; empty means burn
+    #[account(seeds = [b"dao_liquidity", migration.key().as_ref()], bump)]
+    pub dao_liquidity: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Required when a DAOLiquidity account exists
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: PDA that owns the vault and the mint authority
+    #[account(seeds = [b"authority", migration.key().as_ref()], bump = migration.authority_bump)]
+    pub migration_authority: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeGovernance<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub migration: Account<'info, Migration>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + size_of::<Governance>(),
+        seeds = [b"governance", migration.key().as_ref()],
+        bump
+    )]
+    pub governance: Account<'info, Governance>,
+
+    /// Token whose balances carry voting power; must be the migrated token
+    #[account(address = migration.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateGovernance<'info> {
+    pub admin: Signer<'info>,
+
+    pub migration: Account<'info, Migration>,
+
+    #[account(mut, has_one = migration)]
+    pub governance: Account<'info, Governance>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptGovernanceAdmin<'info> {
+    #[account(mut)]
+    pub migration: Account<'info, Migration>,
+
+    #[account(
+        seeds = [b"governance", migration.key().as_ref()],
+        bump = governance.bump,
+        has_one = migration
+    )]
+    pub governance: Account<'info, Governance>,
+}
+
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(mut)]
+    pub governance: Account<'info, Governance>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + size_of::<Proposal>() + MAX_PROPOSAL_ACTIONS * size_of::<ProposalAction>(),
+        seeds = [b"proposal", governance.key().as_ref(), &governance.proposal_count.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(mut)]
+    pub governance: Account<'info, Governance>,
+
+    #[account(mut, has_one = governance)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + size_of::<VoteRecord>(),
+        seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    #[account(
+        seeds = [b"vote_escrow", governance.key().as_ref(), voter.key().as_ref()],
+        bump = vote_escrow.bump
+    )]
+    pub vote_escrow: Account<'info, VoteEscrow>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenVoteEscrow<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub governance: Account<'info, Governance>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + size_of::<VoteEscrow>(),
+        seeds = [b"vote_escrow", governance.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub vote_escrow: Account<'info, VoteEscrow>,
+
+    #[account(
+        init,
+        payer = owner,
+        token::mint = mint,
+        token::authority = vote_escrow,
+        token::token_program = token_program,
+        seeds = [b"vote_escrow_vault", vote_escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = governance.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct LockTokens<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(mut, has_one = owner)]
+    pub vote_escrow: Account<'info, VoteEscrow>,
+
+    #[account(
+        mut,
+        seeds = [b"vote_escrow_vault", vote_escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct UnlockTokens<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(mut, has_one = owner)]
+    pub vote_escrow: Account<'info, VoteEscrow>,
+
+    #[account(
+        mut,
+        seeds = [b"vote_escrow_vault", vote_escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
 
-        msg!("User {} contributed {} tokens to DAO LP pool", ctx.accounts.user.key(), amount);
-        Ok(())
-    }
+    pub token_program: Interface<'info, TokenInterface>,
+}
 
 #[derive(Accounts)]
-#[instruction(name: [u8; 64], source_chain: u16, source_address: [u8; 32], snapshot_root: [u8; 32], total_supply: u64)]
-pub struct InitializeMigration<'info> {
+pub struct Tally<'info> {
     #[account(mut)]
-    pub admin: Signer<'info>,
-    
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(mut)]
+    pub migration: Account<'info, Migration>,
+
     #[account(
-        init,
-        payer = admin,
-        space = 8 + size_of::<Migration>(),
-        seeds = [b"migration", admin.key().as_ref(), &source_chain.to_le_bytes()],
-        bump
+        seeds = [b"governance", migration.key().as_ref()],
+        bump = governance.bump,
+        has_one = migration
     )]
-    pub migration: Account<'info, Migration>,
-    
+    pub governance: Account<'info, Governance>,
+
+    #[account(mut, has_one = governance)]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Required by treasury and pool actions
+    #[account(
+        mut,
+        seeds = [b"dao_liquidity", migration.key().as_ref()],
+        bump = dao_liquidity.bump
+    )]
+    pub dao_liquidity: Option<Account<'info, DAOLiquidity>>,
+
+    /// Pays for the accounts created by `AddSnapshotEpoch` and `QueueOperation`
+    #[account(mut)]
+    pub payer: Option<Signer<'info>>,
+
+    /// Required by `AddSnapshotEpoch`
     #[account(
         init,
-        payer = admin,
-        mint::decimals = 6,
-        mint::authority = migration_authority,
+        payer = payer,
+        space = 8 + size_of::<SnapshotEpoch>(),
+        seeds = [b"epoch", migration.key().as_ref(), &(migration.epoch_count + 1).to_le_bytes()],
+        bump
     )]
-    pub mint: InterfaceAccount<'info, Mint>,
-    
-    /// CHECK: The authority for the migration account
+    pub snapshot_epoch: Option<Account<'info, SnapshotEpoch>>,
+
+    /// Required by `QueueOperation`
     #[account(
-        seeds = [b"authority", migration.key().as_ref()],
+        init,
+        payer = payer,
+        space = 8 + size_of::<QueuedOperation>(),
+        seeds = [b"queued_op", migration.key().as_ref(), &migration.operation_count.to_le_bytes()],
         bump
     )]
-    pub migration_authority: UncheckedAccount<'info>,
-    
-    pub token_program: Interface<'info, TokenInterface>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
+    pub queued_operation: Option<Account<'info, QueuedOperation>>,
+
+    pub system_program: Option<Program<'info, System>>,
 }
 
 #[derive(Accounts)]
-#[instruction(amount: u64, merkle_proof: Vec<[u8; 32]>, leaf_index: u32)]
-pub struct ClaimTokens<'info> {
+pub struct InitializeDAOLiquidity<'info> {
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub admin: Signer<'info>,
     
-    #[account(mut)]
+    /// The creator becomes the DAO treasury, so only the migration admin may create it
+    #[account(constraint = migration.authority == admin.key() @ ErrorCode::Unauthorized)]
     pub migration: Account<'info, Migration>,
     
     #[account(
         init,
-        payer = user,
-        space = 8 + size_of::<UserClaim>(),
-        seeds = [b"claim", migration.key().as_ref(), user.key().as_ref()],
+        payer = admin,
+        space = 8 + size_of::<DAOLiquidity>(),
+        seeds = [b"dao_liquidity", migration.key().as_ref()],
         bump
     )]
-    pub user_claim: Account<'info, UserClaim>,
-    
-    pub mint: InterfaceAccount<'info, Mint>,
-    
-    #[account(mut)]
-    pub token_vault: InterfaceAccount<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
-    
-    /// CHECK: The authority for migrations
-    pub migration_authority: UncheckedAccount<'info>,
+    pub dao_liquidity: Account<'info, DAOLiquidity>,
     
-    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct FinalizeMigration<'info> {
-    #[account(mut)]
-    pub admin: Signer<'info>,
-    
-    #[account(mut)]
+pub struct ProposeDAOTreasury<'info> {
+    pub dao_treasury: Signer<'info>,
+
+    #[account(constraint = migration.timelock_delay == 0 @ ErrorCode::TimelockRequired)]
+    pub migration: Account<'info, Migration>,
+
+    #[account(mut, has_one = migration)]
+    pub dao_liquidity: Account<'info, DAOLiquidity>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptDAOTreasury<'info> {
+    pub new_treasury: Signer<'info>,
+
+    #[account(constraint = migration.timelock_delay == 0 @ ErrorCode::TimelockRequired)]
     pub migration: Account<'info, Migration>,
+
+    #[account(mut, has_one = migration)]
+    pub dao_liquidity: Account<'info, DAOLiquidity>,
 }
 
 #[derive(Accounts)]
-pub struct InitializeDAOLiquidity<'info> {
+pub struct SeedDAOPool<'info> {
     #[account(mut)]
-    pub admin: Signer<'info>,
-    
+    pub dao_treasury: Signer<'info>,
+
+    #[account(has_one = mint)]
     pub migration: Account<'info, Migration>,
-    
+
     #[account(
-        init,
-        payer = admin,
-        space = 8 + size_of::<DAOLiquidity>(),
+        mut,
         seeds = [b"dao_liquidity", migration.key().as_ref()],
-        bump
+        bump = dao_liquidity.bump,
+        has_one = migration,
+        has_one = dao_treasury
     )]
     pub dao_liquidity: Account<'info, DAOLiquidity>,
-    
+
+    /// Migrated token
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Token paired with the migrated token in the pool
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, token::mint = mint, token::authority = dao_liquidity)]
+    pub dao_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, token::mint = quote_mint)]
+    pub treasury_quote_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, token::mint = quote_mint, token::authority = dao_liquidity)]
+    pub quote_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+
+    /// LP tokens land here; no instruction can move them out
+    #[account(
+        init,
+        payer = dao_treasury,
+        token::mint = lp_mint,
+        token::authority = dao_liquidity,
+        token::token_program = token_program,
+        seeds = [b"lp_lock", dao_liquidity.key().as_ref()],
+        bump
+    )]
+    pub lp_lock: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Must have a registered adapter, see `amm::adapter_for`
+    #[account(executable)]
+    pub amm_program: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
@@ -257,26 +2151,52 @@ pub struct ContributeDAOLiquidity<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
     
+    #[account(has_one = mint)]
     pub migration: Account<'info, Migration>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [b"dao_liquidity", migration.key().as_ref()],
+        bump = dao_liquidity.bump,
+        has_one = migration
+    )]
     pub dao_liquidity: Account<'info, DAOLiquidity>,
     
     pub mint: InterfaceAccount<'info, Mint>,
     
-    #[account(mut)]
+    #[account(mut, token::mint = mint)]
     pub user_token_account: InterfaceAccount<'info, TokenAccount>,
     
-    #[account(mut)]
+    #[account(mut, token::mint = mint, token::authority = dao_liquidity)]
     pub dao_vault: InterfaceAccount<'info, TokenAccount>,
     
     pub token_program: Interface<'info, TokenInterface>,
 }
 
+/// Where claimed tokens come from
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SupplyMode {
+    /// `total_supply` is minted into `token_vault` at init and claims transfer out of it
+    Vault,
+    /// Claims mint directly to the claimer; `record_claim` caps minting at `total_supply`
+    MintOnClaim,
+}
+
+/// Where epoch-0 wallet leaves are marked as claimed. Fixed at init so every
+/// leaf has exactly one claim record. EVM and vesting leaves hash differently
+/// and only have one claim path each, so they aren't affected.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClaimTracking {
+    /// A UserClaim PDA per wallet (`claim_tokens`, `claim_tokens_relayed`)
+    UserClaim,
+    /// One bit per leaf in ClaimBitmap chunks (`claim_with_bitmap`)
+    Bitmap,
+}
+
 #[account]
 pub struct Migration {
     pub name: [u8; 64],         // 64 (fixed size)
-    pub admin: Pubkey,          // 32
+    pub admin: Pubkey,          // 32 - Creator, part of the PDA seeds (never changes)
     pub source_chain: u16,      // 2
     pub source_address: [u8; 32], // 32 (fixed size)
     pub snapshot_root: [u8; 32], // 32
@@ -284,19 +2204,354 @@ pub struct Migration {
     pub migrated_amount: u64,   // 8
     pub is_active: bool,        // 1
     pub bump: u8,               // 1
+    pub epoch_count: u32,       // 4  - Snapshot epochs added after init
+    pub claim_deadline: i64,    // 8  - Unix timestamp, 0 = no deadline
+    pub guardian: Pubkey,       // 32 - Can pause, only admin can unpause
+    pub paused: bool,           // 1
+    pub authority: Pubkey,      // 32 - Current controller, transferable
+    pub pending_authority: Pubkey, // 32 - Proposed controller awaiting accept_admin
+    pub timelock_delay: i64,    // 8  - Seconds between queueing and executing sensitive admin operations
+    pub operation_count: u64,   // 8  - Seed for the next QueuedOperation
+    pub mint: Pubkey,           // 32 - Token minted for this migration
+    pub token_vault: Pubkey,    // 32 - Vault claims are paid from, owned by migration_authority
+    pub authority_bump: u8,     // 1  - Bump of the [b"authority", migration] PDA
+    pub supply_mode: SupplyMode, // 1
+    pub decimals: u8,           // 1  - Decimals of the migrated mint
+    pub source_decimals: u8,    // 1  - Decimals of snapshot and VAA amounts
+    pub dust: u128,             // 16 - Source units rounded away when rescaling claims
+    pub hash_version: HashVersion, // 1 - Leaf and node hashing used by snapshot roots
+    pub manifest_hash: [u8; 32], // 32 - sha256 of the snapshot manifest that produced snapshot_root
+    pub claim_tracking: ClaimTracking, // 1 - Where epoch-0 wallet claims are recorded
+}
+
+impl Migration {
+    /// Tokens that can still be claimed before hitting `total_supply`
+    pub fn remaining_supply(&self) -> u64 {
+        self.total_supply.saturating_sub(self.migrated_amount)
+    }
+
+    /// Claims are accepted while active, unpaused and before the deadline (if any)
+    pub fn check_claimable(&self, now: i64) -> Result<()> {
+        require!(self.is_active, ErrorCode::MigrationNotActive);
+        require!(!self.paused, ErrorCode::MigrationPaused);
+        require!(
+            self.claim_deadline == 0 || now < self.claim_deadline,
+            ErrorCode::ClaimDeadlinePassed
+        );
+        Ok(())
+    }
+
+    /// Holders keep at least the window they were promised: a deadline can be
+    /// set or pushed back, never brought forward, and is fixed once it has
+    /// passed since the vault may already have been swept
+    pub fn extend_claim_deadline(&mut self, claim_deadline: i64, now: i64) -> Result<()> {
+        require!(
+            self.claim_deadline == 0 || now < self.claim_deadline,
+            ErrorCode::ClaimDeadlinePassed
+        );
+        require!(
+            claim_deadline > now && claim_deadline > self.claim_deadline,
+            ErrorCode::InvalidOperation
+        );
+        self.claim_deadline = claim_deadline;
+        Ok(())
+    }
+
+    /// When an operation queued at `now` becomes executable
+    pub fn timelock_eta(&self, now: i64) -> Result<i64> {
+        Ok(now.checked_add(self.timelock_delay).ok_or(ErrorCode::InvalidOperation)?)
+    }
+
+    /// Checks shared by `add_snapshot_epoch` and a queued `AddSnapshotEpoch`.
+    /// Returns the number of the epoch being opened.
+    pub fn next_epoch(&mut self, supply_cap: u64, claim_start: i64, claim_end: i64) -> Result<u32> {
+        require!(self.is_active, ErrorCode::MigrationNotActive);
+        require!(supply_cap > 0 && supply_cap <= self.total_supply, ErrorCode::InvalidAmount);
+        require!(claim_start < claim_end, ErrorCode::InvalidOperation);
+        self.epoch_count = self.epoch_count.checked_add(1).ok_or(ErrorCode::InvalidOperation)?;
+        Ok(self.epoch_count)
+    }
+
+    pub fn propose_authority(&mut self, caller: &Pubkey, new_authority: Pubkey) -> Result<()> {
+        require!(self.authority == *caller, ErrorCode::Unauthorized);
+        self.pending_authority = new_authority;
+        Ok(())
+    }
+
+    pub fn accept_authority(&mut self, caller: &Pubkey) -> Result<()> {
+        require!(
+            self.pending_authority != Pubkey::default() && self.pending_authority == *caller,
+            ErrorCode::Unauthorized
+        );
+        self.authority = self.pending_authority;
+        self.pending_authority = Pubkey::default();
+        Ok(())
+    }
+
+    /// Rescale a claim from source units (see the `decimals` module), keep the rounding
+    /// dust, and record it against `total_supply`. Returns the local amount, which
+    /// is never 0: a leaf worth less than one mint unit would otherwise be marked
+    /// claimed without paying anything.
+    pub fn settle_claim(&mut self, source_amount: u64) -> Result<u64> {
+        let (amount, dust) = decimals::rescale(source_amount, self.source_decimals, self.decimals)?;
+        require!(amount > 0, ErrorCode::RescaledToZero);
+        self.record_claim(amount)?;
+        self.dust = self.dust.saturating_add(dust as u128);
+        Ok(amount)
+    }
+
+    /// Replace the snapshot root together with the manifest it was built from
+    pub fn set_snapshot(&mut self, root: [u8; 32], manifest_hash: [u8; 32]) {
+        self.snapshot_root = root;
+        self.manifest_hash = manifest_hash;
+    }
+
+    /// Account for a claim, failing once the sum of claims would exceed `total_supply`
+    pub fn record_claim(&mut self, amount: u64) -> Result<()> {
+        let migrated = self
+            .migrated_amount
+            .checked_add(amount)
+            .ok_or(ErrorCode::SupplyCapExceeded)?;
+        require!(migrated <= self.total_supply, ErrorCode::SupplyCapExceeded);
+        self.migrated_amount = migrated;
+        Ok(())
+    }
+}
+
+#[account]
+pub struct QueuedOperation {
+    pub migration: Pubkey,          // 32
+    pub id: u64,                    // 8
+    pub kind: OperationKind,        // 89 - Largest variant is AddSnapshotEpoch
+    pub eta: i64,                   // 8  - Earliest execution time
+    pub status: OperationStatus,    // 1
+    pub bump: u8,                   // 1
+}
+
+impl QueuedOperation {
+    pub fn check_pending(&self) -> Result<()> {
+        require!(self.status == OperationStatus::Queued, ErrorCode::OperationNotQueued);
+        Ok(())
+    }
+
+    pub fn check_executable(&self, now: i64) -> Result<()> {
+        self.check_pending()?;
+        require!(now >= self.eta, ErrorCode::TimelockNotExpired);
+        Ok(())
+    }
 }
 
 #[account]
 pub struct UserClaim {
     pub user: Pubkey,       // 32
     pub is_claimed: bool,   // 1
-    pub amount: u64,        // 8
+    pub amount: u64,        // 8  - Gross amount from the snapshot
+    pub net_amount: u64,    // 8  - Received after the DAO LP carve-out
+}
+
+impl UserClaim {
+    /// Extra PDA seed for claims against a later epoch. Epoch 0 contributes an
+    /// empty seed so initial-snapshot claims keep their original address.
+    pub fn epoch_seed(epoch_bytes: &[u8; 4]) -> &[u8] {
+        if *epoch_bytes == [0u8; 4] {
+            &[]
+        } else {
+            epoch_bytes
+        }
+    }
+}
+
+#[account]
+pub struct SnapshotEpoch {
+    pub migration: Pubkey,          // 32
+    pub epoch: u32,                 // 4
+    pub snapshot_root: [u8; 32],    // 32
+    pub supply_cap: u64,            // 8  - Max tokens claimable in this epoch
+    pub claimed_amount: u64,        // 8
+    pub claim_start: i64,           // 8  - Unix timestamp
+    pub claim_end: i64,             // 8  - Unix timestamp (exclusive)
+    pub bump: u8,                   // 1
+    pub manifest_hash: [u8; 32],    // 32 - sha256 of the epoch's snapshot manifest
+}
+
+impl SnapshotEpoch {
+    pub fn is_open(&self, now: i64) -> bool {
+        now >= self.claim_start && now < self.claim_end
+    }
+
+    pub fn record_claim(&mut self, amount: u64) -> Result<()> {
+        let claimed = self
+            .claimed_amount
+            .checked_add(amount)
+            .ok_or(ErrorCode::EpochCapExceeded)?;
+        require!(claimed <= self.supply_cap, ErrorCode::EpochCapExceeded);
+        self.claimed_amount = claimed;
+        Ok(())
+    }
+}
+
+#[account(zero_copy)]
+pub struct ClaimBitmap {
+    pub migration: Pubkey,                        // 32
+    pub chunk_index: u32,                         // 4
+    pub claimed_count: u32,                       // 4
+    pub bump: u8,                                 // 1
+    pub _padding: [u8; 7],                        // 7
+    pub bits: [u8; CLAIM_BITMAP_CHUNK_BYTES],     // 4096 - one bit per leaf
+}
+
+impl ClaimBitmap {
+    /// Chunk index holding `leaf_index` and the bit offset inside that chunk
+    pub fn position(leaf_index: u32) -> (u32, usize) {
+        (
+            leaf_index / CLAIM_BITMAP_CHUNK_LEAVES,
+            (leaf_index % CLAIM_BITMAP_CHUNK_LEAVES) as usize,
+        )
+    }
+
+    pub fn is_claimed(&self, bit: usize) -> bool {
+        self.bits[bit / 8] & (1 << (bit % 8)) != 0
+    }
+
+    pub fn set_claimed(&mut self, bit: usize) -> Result<()> {
+        require!(!self.is_claimed(bit), ErrorCode::AlreadyClaimed);
+        self.bits[bit / 8] |= 1 << (bit % 8);
+        self.claimed_count += 1;
+        Ok(())
+    }
+}
+
+#[account]
+pub struct VestingEscrow {
+    pub migration: Pubkey,          // 32
+    pub beneficiary: Pubkey,        // 32
+    pub total_amount: u64,          // 8  - Locked for vesting, after the DAO LP carve-out
+    pub withdrawn: u64,             // 8  - Released so far
+    pub schedule: VestingSchedule,  // 24
+    pub bump: u8,                   // 1
+    pub gross_amount: u64,          // 8  - Allocation from the vesting leaf, in mint units
+}
+
+#[account]
+pub struct VaaClaim {
+    pub migration: Pubkey,  // 32
+    pub sequence: u64,      // 8  - Wormhole sequence of the redeemed VAA
+    pub recipient: Pubkey,  // 32
+    pub amount: u64,        // 8  - Gross amount attested by the VAA, in mint units
+    pub bump: u8,           // 1
+    pub net_amount: u64,    // 8  - Received after the DAO LP carve-out
 }
 
 #[account]
 pub struct Governance {
     pub migration: Pubkey,
-    pub total_votes: u64,
+    pub total_votes: u64,       // 8  - Quadratic weight cast across all proposals
+    pub mint: Pubkey,           // 32 - Token that carries voting power
+    pub proposal_count: u64,    // 8
+    pub voting_period: i64,     // 8  - Seconds
+    pub bump: u8,               // 1
+    pub quorum_voters: u32,     // 4  - Min distinct wallets for a proposal to pass
+    pub quorum_weight: u64,     // 8  - Min total quadratic weight for a proposal to pass
+    pub execution_delay: i64,   // 8  - Timelock between voting end and execution
+}
+
+#[account]
+pub struct Proposal {
+    pub governance: Pubkey,         // 32
+    pub id: u64,                    // 8
+    pub proposer: Pubkey,           // 32
+    pub description_hash: [u8; 32], // 32 - Hash of the off-chain proposal text
+    pub votes_for: u64,             // 8  - Quadratic weight
+    pub votes_against: u64,         // 8  - Quadratic weight
+    pub voting_starts: i64,         // 8  - Also the voting power checkpoint
+    pub voting_ends: i64,           // 8
+    pub status: ProposalStatus,     // 1
+    pub bump: u8,                   // 1
+    pub unique_voters: u32,         // 4
+    pub quorum_voters: u32,         // 4  - Copied from Governance at creation
+    pub quorum_weight: u64,         // 8  - Copied from Governance at creation
+    pub executable_at: i64,         // 8  - voting_ends + Governance.execution_delay
+    pub actions: Vec<ProposalAction>, // 4 + up to MAX_PROPOSAL_ACTIONS actions
+}
+
+impl Proposal {
+    pub fn check_quorum(&self) -> Result<()> {
+        require!(self.unique_voters >= self.quorum_voters, ErrorCode::QuorumVotersNotReached);
+        let total_weight = self.votes_for.saturating_add(self.votes_against);
+        require!(total_weight >= self.quorum_weight, ErrorCode::QuorumWeightNotReached);
+        Ok(())
+    }
+
+    /// Simple majority of quadratic weight once quorum is met; ties fail
+    pub fn outcome(&self) -> ProposalStatus {
+        if self.check_quorum().is_ok() && self.votes_for > self.votes_against {
+            ProposalStatus::Passed
+        } else {
+            ProposalStatus::Rejected
+        }
+    }
+
+    pub fn check_executable(&self, now: i64) -> Result<()> {
+        require!(self.status == ProposalStatus::Passed, ErrorCode::ProposalNotPassed);
+        require!(now >= self.executable_at, ErrorCode::TimelockNotExpired);
+        Ok(())
+    }
+}
+
+#[account]
+pub struct VoteEscrow {
+    pub governance: Pubkey,     // 32
+    pub owner: Pubkey,          // 32
+    pub amount: u64,            // 8  - Tokens currently locked
+    pub locked_at: i64,         // 8  - Time of the latest deposit
+    pub bump: u8,               // 1
+    pub prior_amount: u64,      // 8  - Balance before the latest deposit
+    pub prior_locked_at: i64,   // 8  - Time of the deposit before the latest
+}
+
+impl VoteEscrow {
+    pub fn lock(&mut self, amount: u64, now: i64) -> Result<()> {
+        // Same-second deposits share a checkpoint, so the prior balance stays
+        if self.locked_at < now {
+            self.prior_amount = self.amount;
+            self.prior_locked_at = self.locked_at;
+        }
+        self.amount = self.amount.checked_add(amount).ok_or(ErrorCode::InvalidAmount)?;
+        self.locked_at = now;
+        Ok(())
+    }
+
+    /// Withdrawn tokens come off the prior balance too
+    pub fn unlock(&mut self, amount: u64) -> Result<()> {
+        require!(amount > 0 && amount <= self.amount, ErrorCode::InvalidAmount);
+        self.amount -= amount;
+        self.prior_amount = self.prior_amount.min(self.amount);
+        Ok(())
+    }
+
+    /// Locked balance that counts for a proposal created at `checkpoint`.
+    /// Deposits in the same second as the proposal don't count, but a top-up
+    /// after it doesn't take away the balance that was already locked. Only
+    /// the last two deposits are tracked, so older balances fall back to 0.
+    pub fn voting_power_at(&self, checkpoint: i64) -> u64 {
+        if self.locked_at < checkpoint {
+            self.amount
+        } else if self.prior_locked_at < checkpoint {
+            self.prior_amount
+        } else {
+            0
+        }
+    }
+}
+
+#[account]
+pub struct VoteRecord {
+    pub proposal: Pubkey,   // 32
+    pub voter: Pubkey,      // 32
+    pub stake: u64,         // 8  - Checkpointed escrow balance
+    pub weight: u64,        // 8  - isqrt(stake)
+    pub support: bool,      // 1
+    pub bump: u8,           // 1
 }
 
 #[account]
@@ -307,6 +2562,50 @@ pub struct DAOLiquidity {
     pub pool_initialized: bool, // 1  - Whether LP was created
     pub total_reserved: u64,    // 8  - Total tokens in DAO LP
     pub bump: u8,               // 1  - PDA bump
+    pub pending_treasury: Pubkey, // 32 - Proposed treasury awaiting accept_dao_treasury
+    pub amm_program: Pubkey,    // 32 - AMM the reserve was seeded into
+    pub lp_mint: Pubkey,        // 32 - LP tokens locked under this account
+}
+
+impl DAOLiquidity {
+    /// Tokens that must be reserved before the pool can be seeded
+    pub fn reserve_target(&self, total_supply: u64) -> u64 {
+        (total_supply as u128 * self.lp_percentage as u128 / 100) as u64
+    }
+
+    /// Part of a claim routed to the DAO LP reserve, rounded down in the user's favour
+    pub fn lp_share(&self, amount: u64) -> u64 {
+        (amount as u128 * self.lp_percentage as u128 / 100) as u64
+    }
+
+    pub fn reserve_reached(&self, total_supply: u64) -> bool {
+        self.total_reserved > 0 && self.total_reserved >= self.reserve_target(total_supply)
+    }
+}
+
+#[event]
+pub struct UnclaimedSwept {
+    pub migration: Pubkey,
+    pub amount: u64,
+    pub burned: bool,
+    pub destination: Option<Pubkey>,
+}
+
+#[event]
+pub struct BitmapClaimed {
+    pub migration: Pubkey,
+    pub user: Pubkey,
+    pub leaf_index: u32,
+    pub amount: u64,
+    pub net_amount: u64,
+}
+
+#[event]
+pub struct OperationQueued {
+    pub migration: Pubkey,
+    pub operation: Pubkey,
+    pub kind: OperationKind,
+    pub eta: i64,
 }
 
 #[error_code]
@@ -323,4 +2622,220 @@ pub enum ErrorCode {
     InvalidMerkleProof,
     #[msg("Unauthorized")]
     Unauthorized,
+    #[msg("Invalid or unverified VAA")]
+    InvalidVaa,
+    #[msg("VAA emitter does not match migration source")]
+    InvalidEmitter,
+    #[msg("Invalid VAA payload")]
+    InvalidVaaPayload,
+    #[msg("Recipient does not match attested wallet")]
+    InvalidRecipient,
+    #[msg("Claim exceeds remaining migration supply")]
+    SupplyCapExceeded,
+    #[msg("Migration must be finalized first")]
+    MigrationStillActive,
+    #[msg("Missing or invalid signature")]
+    InvalidSignature,
+    #[msg("Invalid vesting schedule")]
+    InvalidVestingSchedule,
+    #[msg("No vested tokens available")]
+    NothingToWithdraw,
+    #[msg("Invalid snapshot epoch")]
+    InvalidEpoch,
+    #[msg("Claim window for this epoch is closed")]
+    ClaimWindowClosed,
+    #[msg("Claim exceeds epoch supply cap")]
+    EpochCapExceeded,
+    #[msg("Claim deadline has passed")]
+    ClaimDeadlinePassed,
+    #[msg("Claim deadline has not been reached")]
+    ClaimDeadlineNotReached,
+    #[msg("Migration is paused")]
+    MigrationPaused,
+    #[msg("Proposal is not active")]
+    ProposalNotActive,
+    #[msg("Voting period has ended")]
+    VotingClosed,
+    #[msg("Voting period has not ended")]
+    VotingStillOpen,
+    #[msg("No tokens locked before the proposal was created")]
+    NoVotingPower,
+    #[msg("Not enough unique wallets voted")]
+    QuorumVotersNotReached,
+    #[msg("Not enough total voting weight")]
+    QuorumWeightNotReached,
+    #[msg("Too many actions in proposal")]
+    TooManyActions,
+    #[msg("Proposal has not passed")]
+    ProposalNotPassed,
+    #[msg("Timelock has not expired")]
+    TimelockNotExpired,
+    #[msg("Operation must go through the timelock queue")]
+    TimelockRequired,
+    #[msg("Operation is not queued")]
+    OperationNotQueued,
+    #[msg("DAO liquidity reserve not reached")]
+    LpReserveNotReached,
+    #[msg("DAO pool already initialized")]
+    PoolAlreadyInitialized,
+    #[msg("No adapter for this AMM program")]
+    UnsupportedAmm,
+    #[msg("Invalid AMM pool accounts")]
+    InvalidAmmAccounts,
+    #[msg("Rescaled amount does not fit in u64")]
+    DecimalOverflow,
+    #[msg("Migration records claims through a different instruction")]
+    ClaimTrackingMismatch,
+    #[msg("Source and mint decimals differ by more than 18")]
+    InvalidDecimals,
+    #[msg("Claim is worth less than one unit of the migrated mint")]
+    RescaledToZero,
+    #[msg("A veto must be the proposal's only action")]
+    VetoNotSoleAction,
+    #[msg("Timelock delay exceeds the maximum")]
+    TimelockDelayTooLong,
+    #[msg("A proposal can queue at most one operation and add at most one epoch")]
+    DuplicateAccountAction,
+}
+
+/// Fill a newly created QueuedOperation under the migration's next id.
+/// Shared by `queue_operation` and the governance `QueueOperation` action.
+fn record_queued_operation(
+    migration: &mut Account<Migration>,
+    operation: &mut Account<QueuedOperation>,
+    bump: u8,
+    kind: OperationKind,
+    now: i64,
+) -> Result<()> {
+    operation.set_inner(QueuedOperation {
+        migration: migration.key(),
+        id: migration.operation_count,
+        kind,
+        eta: migration.timelock_eta(now)?,
+        status: OperationStatus::Queued,
+        bump,
+    });
+    migration.operation_count += 1;
+
+    emit!(OperationQueued {
+        migration: operation.migration,
+        operation: operation.key(),
+        kind,
+        eta: operation.eta,
+    });
+    msg!("Operation {} queued, executable at {}", operation.id, operation.eta);
+    Ok(())
+}
+
+/// Number the next epoch and fill its newly created account. Shared by
+/// `add_snapshot_epoch`, queued `AddSnapshotEpoch` operations and the
+/// governance action of the same name.
+#[allow(clippy::too_many_arguments)]
+fn open_snapshot_epoch(
+    migration: &mut Account<Migration>,
+    snapshot_epoch: &mut Account<SnapshotEpoch>,
+    bump: u8,
+    snapshot_root: [u8; 32],
+    manifest_hash: [u8; 32],
+    supply_cap: u64,
+    claim_start: i64,
+    claim_end: i64,
+) -> Result<()> {
+    let epoch = migration.next_epoch(supply_cap, claim_start, claim_end)?;
+    snapshot_epoch.set_inner(SnapshotEpoch {
+        migration: migration.key(),
+        epoch,
+        snapshot_root,
+        supply_cap,
+        claimed_amount: 0,
+        claim_start,
+        claim_end,
+        bump,
+        manifest_hash,
+    });
+    msg!("Snapshot epoch {} added", epoch);
+    Ok(())
+}
+
+/// Pay out a settled claim. If the migration has a DAOLiquidity account,
+/// `lp_percentage` of `amount` goes to `dao_vault` and counts toward
+/// `total_reserved`; `to` receives the rest. Every claim path goes through here
+/// and always passes the DAOLiquidity PDA, so no path skips the carve-out.
+/// Returns `(net_amount, lp_amount)`.
+#[allow(clippy::too_many_arguments)]
+fn pay_claim<'info>(
+    migration: &Account<'info, Migration>,
+    token_program: &Interface<'info, TokenInterface>,
+    token_vault: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    authority: &UncheckedAccount<'info>,
+    dao_liquidity: &UncheckedAccount<'info>,
+    dao_vault: Option<&InterfaceAccount<'info, TokenAccount>>,
+    amount: u64,
+) -> Result<(u64, u64)> {
+    let lp_amount = if dao_liquidity.data_is_empty() {
+        0
+    } else {
+        require_keys_eq!(*dao_liquidity.owner, crate::ID, ErrorCode::InvalidOperation);
+        let mut dao_liq = DAOLiquidity::try_deserialize(&mut &dao_liquidity.try_borrow_data()?[..])?;
+        let lp_amount = dao_liq.lp_share(amount);
+        dao_liq.total_reserved = dao_liq.total_reserved.checked_add(lp_amount).ok_or(ErrorCode::InvalidOperation)?;
+        dao_liq.try_serialize(&mut &mut dao_liquidity.try_borrow_mut_data()?[..])?;
+
+        let dao_vault = dao_vault.ok_or(ErrorCode::InvalidRecipient)?;
+        require_keys_eq!(dao_vault.owner, dao_liquidity.key(), ErrorCode::InvalidRecipient);
+        require_keys_eq!(dao_vault.mint, mint.key(), ErrorCode::InvalidRecipient);
+        if lp_amount > 0 {
+            pay_from_supply(migration, token_program, token_vault, mint, dao_vault, authority, lp_amount)?;
+        }
+        lp_amount
+    };
+
+    let net_amount = amount - lp_amount;
+    pay_from_supply(migration, token_program, token_vault, mint, to, authority, net_amount)?;
+    Ok((net_amount, lp_amount))
+}
+
+/// Pay `amount` of the migrated token to `to`, signing as migration_authority:
+/// a transfer out of the vault, or a fresh mint in mint-on-claim mode
+fn pay_from_supply<'info>(
+    migration: &Account<'info, Migration>,
+    token_program: &Interface<'info, TokenInterface>,
+    token_vault: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    authority: &UncheckedAccount<'info>,
+    amount: u64,
+) -> Result<()> {
+    let migration_key = migration.key();
+    let seeds = &[b"authority", migration_key.as_ref(), &[migration.authority_bump]];
+    let signer = &[&seeds[..]];
+
+    match migration.supply_mode {
+        SupplyMode::Vault => {
+            let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+                from: token_vault.to_account_info(),
+                mint: mint.to_account_info(),
+                to: to.to_account_info(),
+                authority: authority.to_account_info(),
+            };
+            anchor_spl::token_interface::transfer_checked(
+                CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer),
+                amount,
+                mint.decimals,
+            )
+        }
+        SupplyMode::MintOnClaim => {
+            let cpi_accounts = anchor_spl::token_interface::MintTo {
+                mint: mint.to_account_info(),
+                to: to.to_account_info(),
+                authority: authority.to_account_info(),
+            };
+            anchor_spl::token_interface::mint_to(
+                CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer),
+                amount,
+            )
+        }
+    }
 }
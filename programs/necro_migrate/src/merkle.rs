@@ -0,0 +1,121 @@
+// Merkle snapshot verification shared by every claim path.
+//
+// Leaf and node hashing lives in the `necro_merkle` crate, which off-chain
+// tools use to build the trees; this module adapts it to Solana types and the
+// sha256 syscall. See that crate for the `Legacy` and `V1` hashing schemes.
+//
+// The free functions below are the legacy scheme; claims go through
+// `SnapshotHasher`, which picks the scheme for the migration.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::vesting::VestingSchedule;
+
+pub use necro_merkle::{LEAF_PREFIX, NODE_PREFIX};
+
+/// `necro_merkle` hash function backed by the sha256 syscall
+pub struct SolanaSha256;
+
+impl necro_merkle::HashFn for SolanaSha256 {
+    fn hashv(parts: &[&[u8]]) -> [u8; 32] {
+        hashv(parts).to_bytes()
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HashVersion {
+    /// Undomained leaves and nodes, for roots built before V1
+    Legacy,
+    /// Prefixed leaves and nodes, leaves bound to program id and migration
+    V1,
+}
+
+impl From<HashVersion> for necro_merkle::HashVersion {
+    fn from(version: HashVersion) -> Self {
+        match version {
+            HashVersion::Legacy => necro_merkle::HashVersion::Legacy,
+            HashVersion::V1 => necro_merkle::HashVersion::V1,
+        }
+    }
+}
+
+fn legacy() -> SnapshotHasher {
+    SnapshotHasher::new(HashVersion::Legacy, Pubkey::default())
+}
+
+/// Leaf committed in the snapshot for a Solana wallet
+pub fn leaf_hash(wallet: &Pubkey, amount: u64, leaf_index: u32) -> [u8; 32] {
+    legacy().leaf_hash(wallet, amount, leaf_index)
+}
+
+/// Leaf committed in the snapshot for an EVM holder, built straight from
+/// source-chain balances without a Solana wallet registration step
+pub fn evm_leaf_hash(evm_address: &[u8; 20], amount: u64, leaf_index: u32) -> [u8; 32] {
+    legacy().evm_leaf_hash(evm_address, amount, leaf_index)
+}
+
+/// Leaf for an allocation that unlocks over time. The schedule is part of
+/// the leaf so a vesting allocation can't be claimed through `claim_tokens`.
+pub fn vesting_leaf_hash(
+    wallet: &Pubkey,
+    amount: u64,
+    leaf_index: u32,
+    schedule: &VestingSchedule,
+) -> [u8; 32] {
+    legacy().vesting_leaf_hash(wallet, amount, leaf_index, schedule)
+}
+
+/// Fold a proof from `leaf` up to the root
+pub fn compute_root(leaf: [u8; 32], proof: &[[u8; 32]]) -> [u8; 32] {
+    legacy().compute_root(leaf, proof)
+}
+
+pub fn verify(leaf: [u8; 32], proof: &[[u8; 32]], root: &[u8; 32]) -> bool {
+    legacy().verify(leaf, proof, root)
+}
+
+/// Leaf and node hashing for one migration's snapshots
+#[derive(Clone, Copy)]
+pub struct SnapshotHasher(necro_merkle::SnapshotHasher<SolanaSha256>);
+
+impl SnapshotHasher {
+    pub fn new(version: HashVersion, migration: Pubkey) -> Self {
+        Self(necro_merkle::SnapshotHasher::new(
+            version.into(),
+            crate::ID.to_bytes(),
+            migration.to_bytes(),
+        ))
+    }
+
+    pub fn leaf_hash(&self, wallet: &Pubkey, amount: u64, leaf_index: u32) -> [u8; 32] {
+        self.0.wallet_leaf(&wallet.to_bytes(), amount, leaf_index)
+    }
+
+    pub fn evm_leaf_hash(&self, evm_address: &[u8; 20], amount: u64, leaf_index: u32) -> [u8; 32] {
+        self.0.evm_leaf(evm_address, amount, leaf_index)
+    }
+
+    pub fn vesting_leaf_hash(
+        &self,
+        wallet: &Pubkey,
+        amount: u64,
+        leaf_index: u32,
+        schedule: &VestingSchedule,
+    ) -> [u8; 32] {
+        self.0.vesting_leaf(&wallet.to_bytes(), amount, leaf_index, &schedule.to_leaf_bytes())
+    }
+
+    /// Parent of two nodes, in either order
+    pub fn node_hash(&self, a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        self.0.node(a, b)
+    }
+
+    pub fn compute_root(&self, leaf: [u8; 32], proof: &[[u8; 32]]) -> [u8; 32] {
+        self.0.compute_root(leaf, proof)
+    }
+
+    pub fn verify(&self, leaf: [u8; 32], proof: &[[u8; 32]], root: &[u8; 32]) -> bool {
+        self.0.verify(leaf, proof, root)
+    }
+}
@@ -0,0 +1,82 @@
+// Queued admin operations. Once a migration has a non-zero `timelock_delay`,
+// finalizing, rotating the snapshot root, adding a snapshot epoch, moving the
+// claim deadline, replacing the guardian, moving the DAO treasury and
+// shortening the delay itself must sit in a QueuedOperation until its ETA,
+// giving holders time to react and the guardian or a governance vote time to
+// veto. The immediate paths (`finalize_migration`, `add_snapshot_epoch`,
+// `set_claim_deadline`, `set_guardian`,
+// `propose_dao_treasury`/`accept_dao_treasury`) fail with `TimelockRequired`
+// while a delay is set.
+
+use anchor_lang::prelude::*;
+
+use crate::{DAOLiquidity, ErrorCode, Migration};
+
+/// Longest delay an admin can impose, so queued operations can't be pushed
+/// out of reach (30 days)
+pub const MAX_TIMELOCK_DELAY: i64 = 30 * 24 * 60 * 60;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OperationKind {
+    Finalize,
+    SetSnapshotRoot { root: [u8; 32], manifest_hash: [u8; 32] },
+    SetDaoTreasury { treasury: Pubkey },
+    SetTimelockDelay { delay: i64 },
+    SetClaimDeadline { claim_deadline: i64 },
+    SetGuardian { guardian: Pubkey },
+    /// Executed with the new SnapshotEpoch account, see `execute_operation`
+    AddSnapshotEpoch {
+        snapshot_root: [u8; 32],
+        manifest_hash: [u8; 32],
+        supply_cap: u64,
+        claim_start: i64,
+        claim_end: i64,
+    },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OperationStatus {
+    Queued,
+    Executed,
+    Cancelled,
+    Vetoed,
+}
+
+impl OperationKind {
+    pub fn validate(&self) -> Result<()> {
+        match *self {
+            OperationKind::SetTimelockDelay { delay } => {
+                require!(delay >= 0, ErrorCode::InvalidOperation);
+                require!(delay <= MAX_TIMELOCK_DELAY, ErrorCode::TimelockDelayTooLong);
+            }
+            OperationKind::AddSnapshotEpoch { supply_cap, claim_start, claim_end, .. } => {
+                require!(supply_cap > 0, ErrorCode::InvalidAmount);
+                require!(claim_start < claim_end, ErrorCode::InvalidOperation);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Treasury operations need the migration's DAOLiquidity account.
+    /// `AddSnapshotEpoch` creates an account, so it isn't applied here.
+    pub fn apply(&self, migration: &mut Migration, dao_liquidity: Option<&mut DAOLiquidity>, now: i64) -> Result<()> {
+        match *self {
+            OperationKind::Finalize => migration.is_active = false,
+            OperationKind::SetSnapshotRoot { root, manifest_hash } => migration.set_snapshot(root, manifest_hash),
+            OperationKind::SetDaoTreasury { treasury } => {
+                let dao_liquidity = dao_liquidity.ok_or(ErrorCode::InvalidOperation)?;
+                dao_liquidity.dao_treasury = treasury;
+                dao_liquidity.pending_treasury = Pubkey::default();
+            }
+            OperationKind::SetTimelockDelay { delay } => migration.timelock_delay = delay,
+            OperationKind::SetClaimDeadline { claim_deadline } => {
+                require!(migration.is_active, ErrorCode::MigrationNotActive);
+                migration.extend_claim_deadline(claim_deadline, now)?;
+            }
+            OperationKind::SetGuardian { guardian } => migration.guardian = guardian,
+            OperationKind::AddSnapshotEpoch { .. } => return err!(ErrorCode::InvalidOperation),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+// Linear vesting with an optional cliff for team/treasury leaves.
+//
+// Nothing unlocks before `cliff_ts`; after that the unlocked share grows
+// linearly from `start_ts` until `start_ts + duration`, so reaching the cliff
+// releases everything accrued since the start at once. Amounts round down, so
+// the final withdrawal always picks up the remainder.
+
+use anchor_lang::prelude::*;
+
+use crate::ErrorCode;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VestingSchedule {
+    pub start_ts: i64,
+    pub cliff_ts: i64,
+    pub duration: i64,
+}
+
+impl VestingSchedule {
+    pub fn end_ts(&self) -> i64 {
+        self.start_ts.saturating_add(self.duration)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        require!(self.duration > 0, ErrorCode::InvalidVestingSchedule);
+        require!(
+            self.cliff_ts >= self.start_ts && self.cliff_ts <= self.end_ts(),
+            ErrorCode::InvalidVestingSchedule
+        );
+        Ok(())
+    }
+
+    /// Portion of `total` unlocked at `now`
+    pub fn vested_amount(&self, total: u64, now: i64) -> u64 {
+        if now < self.cliff_ts {
+            return 0;
+        }
+        if now >= self.end_ts() {
+            return total;
+        }
+        let elapsed = (now - self.start_ts) as u128;
+        (total as u128 * elapsed / self.duration as u128) as u64
+    }
+
+    /// Bytes committed in the Merkle leaf
+    pub fn to_leaf_bytes(&self) -> [u8; 24] {
+        let mut bytes = [0u8; 24];
+        bytes[..8].copy_from_slice(&self.start_ts.to_le_bytes());
+        bytes[8..16].copy_from_slice(&self.cliff_ts.to_le_bytes());
+        bytes[16..].copy_from_slice(&self.duration.to_le_bytes());
+        bytes
+    }
+}
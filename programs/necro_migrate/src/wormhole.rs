@@ -0,0 +1,100 @@
+// Minimal reader for Wormhole core bridge PostedVAA accounts.
+//
+// We only need the header fields and the payload, so instead of pulling in the
+// full wormhole SDK we parse the account layout directly:
+//
+//   b"vaa" | vaa_version u8 | consistency_level u8 | vaa_time u32
+//   | signature_set Pubkey | submission_time u32 | nonce u32 | sequence u64
+//   | emitter_chain u16 | emitter_address [u8; 32] | payload Vec<u8>
+//
+// All integers are little-endian (borsh), except inside the payload which is
+// produced by the EVM attestor with abi.encodePacked (big-endian).
+
+use anchor_lang::prelude::*;
+
+use crate::ErrorCode;
+
+#[cfg(feature = "mainnet")]
+pub const CORE_BRIDGE_PROGRAM_ID: Pubkey = pubkey!("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth");
+#[cfg(not(feature = "mainnet"))]
+pub const CORE_BRIDGE_PROGRAM_ID: Pubkey = pubkey!("3u8hJUVTA4jH1wYAyUur7FFZVQ8H635K3tSHHF4ssjQ5");
+
+/// Account discriminator written by the core bridge for verified VAAs.
+/// Unverified messages use b"msg"/b"msu" and must be rejected.
+pub const POSTED_VAA_MAGIC: &[u8; 3] = b"vaa";
+
+/// Payload id emitted by the attestor for a holder claim
+pub const CLAIM_PAYLOAD_ID: u8 = 1;
+
+const HEADER_LEN: usize = 3 + 1 + 1 + 4 + 32 + 4 + 4 + 8 + 2 + 32;
+const CLAIM_PAYLOAD_LEN: usize = 1 + 32 + 8;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PostedVaa {
+    pub consistency_level: u8,
+    pub vaa_time: u32,
+    pub nonce: u32,
+    pub sequence: u64,
+    pub emitter_chain: u16,
+    pub emitter_address: [u8; 32],
+    pub payload: Vec<u8>,
+}
+
+/// Holder claim attested on the source chain
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClaimPayload {
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+impl PostedVaa {
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        require!(data.len() >= HEADER_LEN + 4, ErrorCode::InvalidVaa);
+        require!(&data[..3] == POSTED_VAA_MAGIC, ErrorCode::InvalidVaa);
+
+        let consistency_level = data[4];
+        let vaa_time = u32::from_le_bytes(data[5..9].try_into().unwrap());
+        // data[9..41] is the signature set, data[41..45] the submission time
+        let nonce = u32::from_le_bytes(data[45..49].try_into().unwrap());
+        let sequence = u64::from_le_bytes(data[49..57].try_into().unwrap());
+        let emitter_chain = u16::from_le_bytes(data[57..59].try_into().unwrap());
+        let emitter_address: [u8; 32] = data[59..HEADER_LEN].try_into().unwrap();
+
+        let payload_len =
+            u32::from_le_bytes(data[HEADER_LEN..HEADER_LEN + 4].try_into().unwrap()) as usize;
+        let payload = data
+            .get(HEADER_LEN + 4..HEADER_LEN + 4 + payload_len)
+            .ok_or(ErrorCode::InvalidVaa)?
+            .to_vec();
+
+        Ok(Self {
+            consistency_level,
+            vaa_time,
+            nonce,
+            sequence,
+            emitter_chain,
+            emitter_address,
+            payload,
+        })
+    }
+
+    /// Only the source token registered on the migration may attest claims
+    pub fn check_emitter(&self, chain: u16, address: &[u8; 32]) -> Result<()> {
+        require!(
+            self.emitter_chain == chain && &self.emitter_address == address,
+            ErrorCode::InvalidEmitter
+        );
+        Ok(())
+    }
+}
+
+impl ClaimPayload {
+    pub fn parse(payload: &[u8]) -> Result<Self> {
+        require!(payload.len() == CLAIM_PAYLOAD_LEN, ErrorCode::InvalidVaaPayload);
+        require!(payload[0] == CLAIM_PAYLOAD_ID, ErrorCode::InvalidVaaPayload);
+
+        let recipient = Pubkey::new_from_array(payload[1..33].try_into().unwrap());
+        let amount = u64::from_be_bytes(payload[33..41].try_into().unwrap());
+        Ok(Self { recipient, amount })
+    }
+}
@@ -0,0 +1,536 @@
+#![cfg(test)]
+
+// Runs the Accounts validation of the claim and DAO instructions
+// against native AccountInfos, swapping in foreign accounts one at a time.
+
+use std::collections::BTreeSet;
+use std::sync::Once;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_stubs;
+use anchor_lang::{system_program, Accounts, AnchorSerialize, Bumps, Discriminator};
+use bytemuck::Zeroable;
+use necro_migrate::{
+    AddSnapshotEpoch, ClaimBitmap, ClaimTokens, ClaimTokensRelayed, ClaimTracking, ClaimWithBitmap, ContributeDAOLiquidity, DAOLiquidity, ErrorCode,
+    Governance, InitializeDAOLiquidity, InitializeGovernance, Migration, ProposeDAOTreasury, RevokeMintAuthority, SeedDAOPool, UserClaim,
+};
+
+mod common;
+use common::{infos, TestAccount};
+
+type AnchorErrorCode = anchor_lang::error::ErrorCode;
+
+/// `init` reads the Rent sysvar, which has no default off-chain. CPIs to the
+/// system program are no-ops; accounts are pre-assigned to the program instead.
+struct RentStubs;
+
+impl program_stubs::SyscallStubs for RentStubs {
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        anchor_lang::solana_program::entrypoint::SUCCESS
+    }
+}
+
+fn install_stubs() {
+    static STUBS: Once = Once::new();
+    STUBS.call_once(|| {
+        program_stubs::set_syscall_stubs(Box::new(RentStubs));
+    });
+}
+
+fn migration_state(mint: Pubkey, token_vault: Pubkey, authority_bump: u8) -> Migration {
+    Migration { total_supply: 1_000_000, mint, token_vault, authority_bump, ..common::migration() }
+}
+
+/// Accounts for a valid ClaimTokens call, in declaration order
+struct ClaimFixture {
+    user: TestAccount,
+    migration: TestAccount,
+    user_claim: TestAccount,
+    mint: TestAccount,
+    token_vault: TestAccount,
+    user_token_account: TestAccount,
+    dao_liquidity: TestAccount,
+    migration_authority: TestAccount,
+}
+
+impl ClaimFixture {
+    fn new() -> Self {
+        let user = Pubkey::new_unique();
+        let migration = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let (authority, authority_bump) =
+            Pubkey::find_program_address(&[b"authority", migration.as_ref()], &necro_migrate::ID);
+        let vault = TestAccount::token_account(mint, authority);
+        let (user_claim, _) = Pubkey::find_program_address(
+            &[b"claim", migration.as_ref(), user.as_ref(), UserClaim::epoch_seed(&0u32.to_le_bytes())],
+            &necro_migrate::ID,
+        );
+        let (dao_liquidity, _) =
+            Pubkey::find_program_address(&[b"dao_liquidity", migration.as_ref()], &necro_migrate::ID);
+
+        Self {
+            user: TestAccount::signer(user),
+            migration: TestAccount::anchor(migration, &migration_state(mint, vault.key, authority_bump)),
+            user_claim: TestAccount::new(
+                user_claim,
+                necro_migrate::ID,
+                vec![0u8; 8 + std::mem::size_of::<UserClaim>()],
+            ),
+            mint: TestAccount::mint(mint, authority),
+            token_vault: vault,
+            user_token_account: TestAccount::token_account(mint, user),
+            dao_liquidity: TestAccount::new(dao_liquidity, system_program::ID, vec![]),
+            migration_authority: TestAccount::new(authority, system_program::ID, vec![]),
+        }
+    }
+
+    fn update_migration(&mut self, update: impl FnOnce(&mut Migration)) {
+        let mut state = Migration::try_deserialize(&mut &self.migration.data[..]).unwrap();
+        update(&mut state);
+        self.migration = TestAccount::anchor(self.migration.key, &state);
+    }
+
+    /// ClaimWithBitmap for leaf 0 with the same migration accounts
+    fn try_bitmap_claim(&self, bitmap: &ClaimBitmap) -> Result<()> {
+        let (key, _) = Pubkey::find_program_address(
+            &[b"claim_bitmap", self.migration.key.as_ref(), &0u32.to_le_bytes()],
+            &necro_migrate::ID,
+        );
+        let mut data = ClaimBitmap::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(bytemuck::bytes_of(bitmap));
+        let mut accounts = vec![
+            self.user.clone(),
+            self.migration.clone(),
+            TestAccount::new(key, necro_migrate::ID, data),
+            self.mint.clone(),
+            self.token_vault.clone(),
+            self.user_token_account.clone(),
+            self.dao_liquidity.clone(),
+            TestAccount::none(),
+            self.migration_authority.clone(),
+            TestAccount::program(spl_token::ID),
+        ];
+        let infos = infos(&mut accounts);
+        // amount, merkle_proof, leaf_index
+        let ix_data = (100u64, Vec::<[u8; 32]>::new(), 0u32).try_to_vec().unwrap();
+        let mut bumps = <ClaimWithBitmap as Bumps>::Bumps::default();
+        ClaimWithBitmap::try_accounts(&necro_migrate::ID, &mut &infos[..], &ix_data, &mut bumps, &mut BTreeSet::new())
+            .map(|_| ())
+    }
+
+    /// ClaimTokensRelayed with `user` as the beneficiary and a separate relayer
+    fn try_relayed_claim(self) -> Result<()> {
+        install_stubs();
+        let mut accounts = vec![
+            TestAccount::signer(Pubkey::new_unique()),
+            TestAccount { is_signer: false, ..self.user },
+            self.migration,
+            self.user_claim,
+            self.mint,
+            self.token_vault,
+            self.user_token_account,
+            self.dao_liquidity,
+            TestAccount::none(),
+            self.migration_authority,
+            TestAccount::new(anchor_lang::solana_program::sysvar::instructions::ID, Pubkey::default(), vec![]),
+            TestAccount::program(spl_token::ID),
+            TestAccount::program(system_program::ID),
+        ];
+        let infos = infos(&mut accounts);
+        // amount, merkle_proof, leaf_index
+        let ix_data = (100u64, Vec::<[u8; 32]>::new(), 0u32).try_to_vec().unwrap();
+        let mut bumps = <ClaimTokensRelayed as Bumps>::Bumps::default();
+        ClaimTokensRelayed::try_accounts(&necro_migrate::ID, &mut &infos[..], &ix_data, &mut bumps, &mut BTreeSet::new())
+            .map(|_| ())
+    }
+
+    fn try_accounts(self) -> Result<()> {
+        install_stubs();
+        let mut accounts = vec![
+            self.user,
+            self.migration,
+            self.user_claim,
+            TestAccount::none(),
+            self.mint,
+            self.token_vault,
+            self.user_token_account,
+            self.dao_liquidity,
+            TestAccount::none(),
+            self.migration_authority,
+            TestAccount::program(spl_token::ID),
+            TestAccount::program(system_program::ID),
+        ];
+        let infos = infos(&mut accounts);
+        // amount, merkle_proof, leaf_index, epoch
+        let ix_data = (100u64, Vec::<[u8; 32]>::new(), 0u32, 0u32).try_to_vec().unwrap();
+        let mut bumps = <ClaimTokens as Bumps>::Bumps::default();
+        ClaimTokens::try_accounts(&necro_migrate::ID, &mut &infos[..], &ix_data, &mut bumps, &mut BTreeSet::new())
+            .map(|_| ())
+    }
+}
+
+/// Accounts for a valid ContributeDAOLiquidity call, in declaration order
+struct ContributeFixture {
+    user: TestAccount,
+    migration: TestAccount,
+    dao_liquidity: TestAccount,
+    mint: TestAccount,
+    user_token_account: TestAccount,
+    dao_vault: TestAccount,
+}
+
+fn dao_liquidity_state(migration: Pubkey, bump: u8) -> DAOLiquidity {
+    DAOLiquidity {
+        migration,
+        dao_treasury: Pubkey::new_unique(),
+        lp_percentage: 10,
+        pool_initialized: false,
+        total_reserved: 0,
+        bump,
+        pending_treasury: Pubkey::default(),
+        amm_program: Pubkey::default(),
+        lp_mint: Pubkey::default(),
+    }
+}
+
+impl ContributeFixture {
+    fn new() -> Self {
+        let user = Pubkey::new_unique();
+        let migration = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let (dao_liquidity, dao_bump) =
+            Pubkey::find_program_address(&[b"dao_liquidity", migration.as_ref()], &necro_migrate::ID);
+
+        Self {
+            user: TestAccount::signer(user),
+            migration: TestAccount::anchor(migration, &migration_state(mint, Pubkey::new_unique(), 255)),
+            dao_liquidity: TestAccount::anchor(dao_liquidity, &dao_liquidity_state(migration, dao_bump)),
+            mint: TestAccount::mint(mint, Pubkey::new_unique()),
+            user_token_account: TestAccount::token_account(mint, user),
+            dao_vault: TestAccount::token_account(mint, dao_liquidity),
+        }
+    }
+
+    fn try_accounts(self) -> Result<()> {
+        let mut accounts = vec![
+            self.user,
+            self.migration,
+            self.dao_liquidity,
+            self.mint,
+            self.user_token_account,
+            self.dao_vault,
+            TestAccount::program(spl_token::ID),
+        ];
+        let infos = infos(&mut accounts);
+        let mut bumps = <ContributeDAOLiquidity as Bumps>::Bumps::default();
+        ContributeDAOLiquidity::try_accounts(&necro_migrate::ID, &mut &infos[..], &[], &mut bumps, &mut BTreeSet::new())
+            .map(|_| ())
+    }
+}
+
+#[test]
+fn test_claim_accepts_migration_accounts() {
+    ClaimFixture::new().try_accounts().unwrap();
+    println!("✓ ClaimTokens accepts the migration's own accounts");
+}
+
+#[test]
+fn test_claim_rejects_foreign_vault() {
+    let mut fixture = ClaimFixture::new();
+    // Same mint, same authority, but not the vault recorded on the migration
+    let authority = fixture.migration_authority.key;
+    fixture.token_vault = TestAccount::token_account(fixture.mint.key, authority);
+    assert_eq!(fixture.try_accounts().unwrap_err(), AnchorErrorCode::ConstraintHasOne.into());
+}
+
+#[test]
+fn test_claim_rejects_foreign_mint() {
+    let mut fixture = ClaimFixture::new();
+    fixture.mint = TestAccount::mint(Pubkey::new_unique(), fixture.migration_authority.key);
+    assert_eq!(fixture.try_accounts().unwrap_err(), AnchorErrorCode::ConstraintHasOne.into());
+}
+
+#[test]
+fn test_claim_rejects_foreign_authority() {
+    let mut fixture = ClaimFixture::new();
+    fixture.migration_authority = TestAccount::new(Pubkey::new_unique(), system_program::ID, vec![]);
+    assert_eq!(fixture.try_accounts().unwrap_err(), AnchorErrorCode::ConstraintSeeds.into());
+}
+
+#[test]
+fn test_claim_rejects_user_account_for_other_mint() {
+    let mut fixture = ClaimFixture::new();
+    fixture.user_token_account = TestAccount::token_account(Pubkey::new_unique(), fixture.user.key);
+    assert_eq!(fixture.try_accounts().unwrap_err(), AnchorErrorCode::ConstraintTokenMint.into());
+}
+
+#[test]
+fn test_claims_pay_only_the_claimers_token_account() {
+    let mut fixture = ClaimFixture::new();
+    fixture.user_token_account = TestAccount::token_account(fixture.mint.key, Pubkey::new_unique());
+    assert_eq!(fixture.try_accounts().unwrap_err(), AnchorErrorCode::ConstraintTokenOwner.into());
+
+    let mut fixture = ClaimFixture::new();
+    fixture.update_migration(|migration| migration.claim_tracking = ClaimTracking::Bitmap);
+    fixture.user_token_account = TestAccount::token_account(fixture.mint.key, Pubkey::new_unique());
+    let bitmap = claim_bitmap(fixture.migration.key);
+    assert_eq!(fixture.try_bitmap_claim(&bitmap).unwrap_err(), AnchorErrorCode::ConstraintTokenOwner.into());
+
+    // The relayer can't redirect the beneficiary's claim to its own account
+    let mut fixture = ClaimFixture::new();
+    fixture.user_token_account = TestAccount::token_account(fixture.mint.key, Pubkey::new_unique());
+    assert_eq!(fixture.try_relayed_claim().unwrap_err(), AnchorErrorCode::ConstraintTokenOwner.into());
+    println!("✓ Claims only pay token accounts owned by the claiming wallet");
+}
+
+#[test]
+fn test_relayed_claim_cannot_skip_dao_carve_out() {
+    ClaimFixture::new().try_relayed_claim().unwrap();
+
+    // Passing some empty account instead of the DAOLiquidity PDA would skip the LP share
+    let mut fixture = ClaimFixture::new();
+    fixture.dao_liquidity = TestAccount::new(Pubkey::new_unique(), system_program::ID, vec![]);
+    assert_eq!(fixture.try_relayed_claim().unwrap_err(), AnchorErrorCode::ConstraintSeeds.into());
+    println!("✓ Relayed claims must pass the migration's DAOLiquidity PDA");
+}
+
+fn claim_bitmap(migration: Pubkey) -> Box<ClaimBitmap> {
+    let (_, bump) = Pubkey::find_program_address(
+        &[b"claim_bitmap", migration.as_ref(), &0u32.to_le_bytes()],
+        &necro_migrate::ID,
+    );
+    let mut bitmap = Box::new(ClaimBitmap::zeroed());
+    bitmap.migration = migration;
+    bitmap.bump = bump;
+    bitmap
+}
+
+#[test]
+fn test_bitmap_claim_blocks_claim_tokens() {
+    let mut fixture = ClaimFixture::new();
+    fixture.update_migration(|migration| migration.claim_tracking = ClaimTracking::Bitmap);
+
+    // Leaf 0 claimed through the bitmap...
+    let mut bitmap = claim_bitmap(fixture.migration.key);
+    fixture.try_bitmap_claim(&bitmap).unwrap();
+    bitmap.set_claimed(ClaimBitmap::position(0).1).unwrap();
+
+    // ...can't be claimed again through the UserClaim path
+    assert_eq!(fixture.try_accounts().unwrap_err(), ErrorCode::ClaimTrackingMismatch.into());
+    println!("✓ Bitmap migrations reject claim_tokens for the initial snapshot");
+}
+
+#[test]
+fn test_user_claim_migration_rejects_bitmap_claims() {
+    let fixture = ClaimFixture::new();
+    let bitmap = claim_bitmap(fixture.migration.key);
+    assert_eq!(fixture.try_bitmap_claim(&bitmap).unwrap_err(), ErrorCode::ClaimTrackingMismatch.into());
+    fixture.try_accounts().unwrap();
+    println!("✓ UserClaim migrations reject claim_with_bitmap");
+}
+
+#[test]
+fn test_contribute_accepts_dao_accounts() {
+    ContributeFixture::new().try_accounts().unwrap();
+    println!("✓ ContributeDAOLiquidity accepts the DAO's own accounts");
+}
+
+#[test]
+fn test_contribute_rejects_foreign_dao_vault() {
+    let mut fixture = ContributeFixture::new();
+    fixture.dao_vault = TestAccount::token_account(fixture.mint.key, Pubkey::new_unique());
+    assert_eq!(fixture.try_accounts().unwrap_err(), AnchorErrorCode::ConstraintTokenOwner.into());
+}
+
+#[test]
+fn test_contribute_rejects_other_migrations_dao() {
+    let mut fixture = ContributeFixture::new();
+    // A DAOLiquidity belonging to a different migration
+    let other = Pubkey::new_unique();
+    let (key, bump) = Pubkey::find_program_address(&[b"dao_liquidity", other.as_ref()], &necro_migrate::ID);
+    fixture.dao_liquidity = TestAccount::anchor(key, &dao_liquidity_state(other, bump));
+    assert_eq!(fixture.try_accounts().unwrap_err(), AnchorErrorCode::ConstraintSeeds.into());
+}
+
+#[test]
+fn test_contribute_rejects_foreign_mint() {
+    let mut fixture = ContributeFixture::new();
+    let mint = Pubkey::new_unique();
+    fixture.mint = TestAccount::mint(mint, Pubkey::new_unique());
+    fixture.user_token_account = TestAccount::token_account(mint, fixture.user.key);
+    fixture.dao_vault = TestAccount::token_account(mint, fixture.dao_liquidity.key);
+    assert_eq!(fixture.try_accounts().unwrap_err(), AnchorErrorCode::ConstraintHasOne.into());
+}
+
+fn try_initialize_dao_liquidity(admin: Pubkey, migration: &Migration) -> Result<()> {
+    install_stubs();
+    let migration_key = Pubkey::new_unique();
+    let (dao_liquidity, _) =
+        Pubkey::find_program_address(&[b"dao_liquidity", migration_key.as_ref()], &necro_migrate::ID);
+    let mut accounts = vec![
+        TestAccount::signer(admin),
+        TestAccount::anchor(migration_key, migration),
+        TestAccount::new(dao_liquidity, necro_migrate::ID, vec![0u8; 8 + std::mem::size_of::<DAOLiquidity>()]),
+        TestAccount::program(system_program::ID),
+    ];
+    let infos = infos(&mut accounts);
+    let mut bumps = <InitializeDAOLiquidity as Bumps>::Bumps::default();
+    InitializeDAOLiquidity::try_accounts(&necro_migrate::ID, &mut &infos[..], &[], &mut bumps, &mut BTreeSet::new())
+        .map(|_| ())
+}
+
+#[test]
+fn test_only_admin_initializes_dao_liquidity() {
+    let migration = common::migration();
+    try_initialize_dao_liquidity(migration.authority, &migration).unwrap();
+
+    // Whoever creates it becomes dao_treasury and receives swept tokens
+    let err = try_initialize_dao_liquidity(Pubkey::new_unique(), &migration).unwrap_err();
+    assert_eq!(err, ErrorCode::Unauthorized.into());
+    println!("✓ Only the migration admin can create the DAOLiquidity account");
+}
+
+fn try_initialize_governance(migration: &Migration, mint: Pubkey) -> Result<()> {
+    install_stubs();
+    let migration_key = Pubkey::new_unique();
+    let (governance, _) = Pubkey::find_program_address(&[b"governance", migration_key.as_ref()], &necro_migrate::ID);
+    let mut accounts = vec![
+        TestAccount::signer(migration.authority),
+        TestAccount::anchor(migration_key, migration),
+        TestAccount::new(governance, necro_migrate::ID, vec![0u8; 8 + std::mem::size_of::<Governance>()]),
+        TestAccount::mint(mint, Pubkey::new_unique()),
+        TestAccount::program(system_program::ID),
+    ];
+    let infos = infos(&mut accounts);
+    let mut bumps = <InitializeGovernance as Bumps>::Bumps::default();
+    InitializeGovernance::try_accounts(&necro_migrate::ID, &mut &infos[..], &[], &mut bumps, &mut BTreeSet::new())
+        .map(|_| ())
+}
+
+#[test]
+fn test_governance_votes_with_migrated_mint() {
+    let migration = common::migration();
+    try_initialize_governance(&migration, migration.mint).unwrap();
+
+    // Any other mint would let holders of an unrelated token vote
+    let err = try_initialize_governance(&migration, Pubkey::new_unique()).unwrap_err();
+    assert_eq!(err, AnchorErrorCode::ConstraintAddress.into());
+    println!("✓ Governance only counts the migrated mint");
+}
+
+fn try_seed_dao_pool(migration: &Migration, mint: Pubkey) -> Result<()> {
+    install_stubs();
+    let migration_key = Pubkey::new_unique();
+    let (dao_liquidity_key, bump) =
+        Pubkey::find_program_address(&[b"dao_liquidity", migration_key.as_ref()], &necro_migrate::ID);
+    let dao_liquidity = dao_liquidity_state(migration_key, bump);
+    let (quote_mint, lp_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let (lp_lock, _) = Pubkey::find_program_address(&[b"lp_lock", dao_liquidity_key.as_ref()], &necro_migrate::ID);
+    let mut accounts = vec![
+        TestAccount::signer(dao_liquidity.dao_treasury),
+        TestAccount::anchor(migration_key, migration),
+        TestAccount::anchor(dao_liquidity_key, &dao_liquidity),
+        TestAccount::mint(mint, Pubkey::new_unique()),
+        TestAccount::mint(quote_mint, Pubkey::new_unique()),
+        TestAccount::token_account(mint, dao_liquidity_key),
+        TestAccount::token_account(quote_mint, dao_liquidity.dao_treasury),
+        TestAccount::token_account(quote_mint, dao_liquidity_key),
+        TestAccount::mint(lp_mint, Pubkey::new_unique()),
+        TestAccount { key: lp_lock, ..TestAccount::token_account(lp_mint, dao_liquidity_key) },
+        TestAccount::program(Pubkey::new_unique()),
+        TestAccount::program(spl_token::ID),
+        TestAccount::program(system_program::ID),
+    ];
+    let infos = infos(&mut accounts);
+    let mut bumps = <SeedDAOPool as Bumps>::Bumps::default();
+    SeedDAOPool::try_accounts(&necro_migrate::ID, &mut &infos[..], &[], &mut bumps, &mut BTreeSet::new())
+        .map(|_| ())
+}
+
+#[test]
+fn test_seed_dao_pool_requires_migrated_mint() {
+    let migration = common::migration();
+    try_seed_dao_pool(&migration, migration.mint).unwrap();
+
+    // A look-alike mint with a matching dao_vault would skip the real reserve
+    let err = try_seed_dao_pool(&migration, Pubkey::new_unique()).unwrap_err();
+    assert_eq!(err, AnchorErrorCode::ConstraintHasOne.into());
+    println!("✓ seed_dao_pool only accepts the migration's mint");
+}
+
+fn try_add_snapshot_epoch(migration: &Migration) -> Result<()> {
+    install_stubs();
+    let migration_key = Pubkey::new_unique();
+    let (snapshot_epoch, _) = Pubkey::find_program_address(
+        &[b"epoch", migration_key.as_ref(), &(migration.epoch_count + 1).to_le_bytes()],
+        &necro_migrate::ID,
+    );
+    let mut accounts = vec![
+        TestAccount::signer(migration.authority),
+        TestAccount::anchor(migration_key, migration),
+        TestAccount::new(
+            snapshot_epoch,
+            necro_migrate::ID,
+            vec![0u8; 8 + std::mem::size_of::<necro_migrate::SnapshotEpoch>()],
+        ),
+        TestAccount::program(system_program::ID),
+    ];
+    let infos = infos(&mut accounts);
+    let mut bumps = <AddSnapshotEpoch as Bumps>::Bumps::default();
+    AddSnapshotEpoch::try_accounts(&necro_migrate::ID, &mut &infos[..], &[], &mut bumps, &mut BTreeSet::new())
+        .map(|_| ())
+}
+
+fn try_propose_dao_treasury(migration: &Migration) -> Result<()> {
+    let migration_key = Pubkey::new_unique();
+    let dao_liquidity = dao_liquidity_state(migration_key, 255);
+    let mut accounts = vec![
+        TestAccount::signer(dao_liquidity.dao_treasury),
+        TestAccount::anchor(migration_key, migration),
+        TestAccount::anchor(Pubkey::new_unique(), &dao_liquidity),
+    ];
+    let infos = infos(&mut accounts);
+    let mut bumps = <ProposeDAOTreasury as Bumps>::Bumps::default();
+    ProposeDAOTreasury::try_accounts(&necro_migrate::ID, &mut &infos[..], &[], &mut bumps, &mut BTreeSet::new())
+        .map(|_| ())
+}
+
+#[test]
+fn test_timelock_blocks_immediate_epoch_and_treasury_changes() {
+    let migration = common::migration();
+    try_add_snapshot_epoch(&migration).unwrap();
+    try_propose_dao_treasury(&migration).unwrap();
+
+    let timelocked = Migration { timelock_delay: 86_400, ..migration };
+    assert_eq!(try_add_snapshot_epoch(&timelocked).unwrap_err(), ErrorCode::TimelockRequired.into());
+    assert_eq!(try_propose_dao_treasury(&timelocked).unwrap_err(), ErrorCode::TimelockRequired.into());
+    println!("✓ Epochs and treasury handovers can't bypass the timelock");
+}
+
+fn try_revoke(migration: TestAccount, mint: TestAccount, migration_authority: TestAccount) -> Result<()> {
+    let mut accounts = vec![migration, mint, migration_authority, TestAccount::program(spl_token::ID)];
+    let infos = infos(&mut accounts);
+    let mut bumps = <RevokeMintAuthority as Bumps>::Bumps::default();
+    RevokeMintAuthority::try_accounts(&necro_migrate::ID, &mut &infos[..], &[], &mut bumps, &mut BTreeSet::new())
+        .map(|_| ())
+}
+
+#[test]
+fn test_revoke_mint_authority_targets_migration_mint() {
+    let fixture = ClaimFixture::new();
+    let authority = fixture.migration_authority.key;
+    try_revoke(fixture.migration.clone(), fixture.mint.clone(), fixture.migration_authority.clone()).unwrap();
+
+    let foreign_mint = TestAccount::mint(Pubkey::new_unique(), authority);
+    assert_eq!(
+        try_revoke(fixture.migration.clone(), foreign_mint, fixture.migration_authority.clone()).unwrap_err(),
+        AnchorErrorCode::ConstraintHasOne.into()
+    );
+
+    let foreign_authority = TestAccount::new(Pubkey::new_unique(), system_program::ID, vec![]);
+    assert_eq!(
+        try_revoke(fixture.migration, fixture.mint, foreign_authority).unwrap_err(),
+        AnchorErrorCode::ConstraintSeeds.into()
+    );
+}
@@ -0,0 +1,65 @@
+#![cfg(test)]
+
+use bytemuck::Zeroable;
+use necro_migrate::{ClaimBitmap, ErrorCode, CLAIM_BITMAP_CHUNK_BYTES, CLAIM_BITMAP_CHUNK_LEAVES};
+use solana_sdk::pubkey::Pubkey;
+
+#[test]
+fn test_leaf_index_to_chunk_position() {
+    assert_eq!(ClaimBitmap::position(0), (0, 0));
+    assert_eq!(ClaimBitmap::position(CLAIM_BITMAP_CHUNK_LEAVES - 1), (0, CLAIM_BITMAP_CHUNK_LEAVES as usize - 1));
+    assert_eq!(ClaimBitmap::position(CLAIM_BITMAP_CHUNK_LEAVES), (1, 0));
+    assert_eq!(ClaimBitmap::position(100_000), (3, 100_000 - 3 * CLAIM_BITMAP_CHUNK_LEAVES as usize));
+
+    // 100k holders fit in 4 chunks
+    assert_eq!(CLAIM_BITMAP_CHUNK_LEAVES, 32_768);
+    println!("✓ Leaf index maps to chunk/bit");
+}
+
+#[test]
+fn test_set_claimed_rejects_double_claim() {
+    let mut bitmap = Box::new(ClaimBitmap::zeroed());
+
+    bitmap.set_claimed(9).unwrap();
+    assert!(bitmap.is_claimed(9));
+    assert!(!bitmap.is_claimed(8));
+    assert!(!bitmap.is_claimed(10));
+    assert_eq!(bitmap.bits[1], 0b0000_0010);
+
+    let err = bitmap.set_claimed(9).unwrap_err();
+    assert_eq!(err, ErrorCode::AlreadyClaimed.into());
+    assert_eq!(bitmap.claimed_count, 1);
+
+    let last = CLAIM_BITMAP_CHUNK_LEAVES as usize - 1;
+    bitmap.set_claimed(last).unwrap();
+    assert_eq!(bitmap.bits[CLAIM_BITMAP_CHUNK_BYTES - 1], 0b1000_0000);
+    assert_eq!(bitmap.claimed_count, 2);
+
+    println!("✓ Bitmap prevents double claims");
+}
+
+#[test]
+fn test_claim_bitmap_account_size() {
+    // Fits under the 10KiB CPI allocation limit for `init`
+    let size = 8 + std::mem::size_of::<ClaimBitmap>();
+    assert_eq!(size, 8 + 48 + CLAIM_BITMAP_CHUNK_BYTES);
+    assert!(size <= 10_240);
+
+    println!("✓ ClaimBitmap: {} bytes per {} leaves", size, CLAIM_BITMAP_CHUNK_LEAVES);
+}
+
+#[test]
+fn test_claim_bitmap_pda_structure() {
+    let migration = Pubkey::new_unique();
+    let (chunk_index, _) = ClaimBitmap::position(40_000);
+
+    let seeds = [
+        b"claim_bitmap".to_vec(),
+        migration.to_bytes().to_vec(),
+        chunk_index.to_le_bytes().to_vec(),
+    ];
+
+    assert_eq!(seeds[0], b"claim_bitmap");
+    assert_eq!(seeds[2], 1u32.to_le_bytes());
+    println!("✓ ClaimBitmap PDA structure verified");
+}
@@ -0,0 +1,125 @@
+// Fixtures shared by the integration tests. Each test file pulls this in with
+// `mod common;` and only uses part of it.
+#![allow(dead_code)]
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::system_program;
+use necro_migrate::merkle::HashVersion;
+use necro_migrate::{ClaimTracking, Migration, SupplyMode};
+
+pub mod runtime;
+
+/// An active, unpaused Vault-mode migration with no timelock. Tests override
+/// what they care about: `Migration { total_supply: 10, ..common::migration() }`
+pub fn migration() -> Migration {
+    Migration {
+        name: [0u8; 64],
+        admin: Pubkey::new_unique(),
+        source_chain: 1,
+        source_address: [0u8; 32],
+        snapshot_root: [1u8; 32],
+        total_supply: 1_000,
+        migrated_amount: 0,
+        is_active: true,
+        bump: 255,
+        epoch_count: 0,
+        claim_deadline: 0,
+        guardian: Pubkey::new_unique(),
+        paused: false,
+        authority: Pubkey::new_unique(),
+        pending_authority: Pubkey::default(),
+        timelock_delay: 0,
+        operation_count: 0,
+        mint: Pubkey::new_unique(),
+        token_vault: Pubkey::new_unique(),
+        authority_bump: 255,
+        supply_mode: SupplyMode::Vault,
+        decimals: 6,
+        source_decimals: 6,
+        dust: 0,
+        hash_version: HashVersion::V1,
+        manifest_hash: [0u8; 32],
+        claim_tracking: ClaimTracking::UserClaim,
+    }
+}
+
+/// Owned backing storage for a native AccountInfo
+#[derive(Clone)]
+pub struct TestAccount {
+    pub key: Pubkey,
+    pub owner: Pubkey,
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub is_signer: bool,
+    pub executable: bool,
+}
+
+impl TestAccount {
+    pub fn new(key: Pubkey, owner: Pubkey, data: Vec<u8>) -> Self {
+        Self { key, owner, lamports: 1_000_000_000, data, is_signer: false, executable: false }
+    }
+
+    pub fn signer(key: Pubkey) -> Self {
+        Self { is_signer: true, ..Self::new(key, system_program::ID, vec![]) }
+    }
+
+    pub fn program(key: Pubkey) -> Self {
+        Self { executable: true, ..Self::new(key, Pubkey::default(), vec![]) }
+    }
+
+    /// Anchor's placeholder for an omitted optional account
+    pub fn none() -> Self {
+        Self::new(necro_migrate::ID, Pubkey::default(), vec![])
+    }
+
+    pub fn anchor<T: AccountSerialize>(key: Pubkey, account: &T) -> Self {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        Self::new(key, necro_migrate::ID, data)
+    }
+
+    pub fn mint(key: Pubkey, authority: Pubkey) -> Self {
+        let mint = spl_token::state::Mint {
+            mint_authority: COption::Some(authority),
+            supply: 1_000_000,
+            decimals: 6,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        let mut data = vec![0u8; spl_token::state::Mint::LEN];
+        mint.pack_into_slice(&mut data);
+        Self::new(key, spl_token::ID, data)
+    }
+
+    pub fn token_account(mint: Pubkey, owner: Pubkey) -> Self {
+        let account = spl_token::state::Account {
+            mint,
+            owner,
+            amount: 1_000_000,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        };
+        let mut data = vec![0u8; spl_token::state::Account::LEN];
+        account.pack_into_slice(&mut data);
+        Self::new(Pubkey::new_unique(), spl_token::ID, data)
+    }
+
+    pub fn info(&mut self) -> AccountInfo<'_> {
+        AccountInfo::new(
+            &self.key,
+            self.is_signer,
+            true,
+            &mut self.lamports,
+            &mut self.data,
+            &self.owner,
+            self.executable,
+            0,
+        )
+    }
+}
+
+pub fn infos(accounts: &mut [TestAccount]) -> Vec<AccountInfo<'_>> {
+    accounts.iter_mut().map(TestAccount::info).collect()
+}
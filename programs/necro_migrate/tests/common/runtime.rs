@@ -0,0 +1,296 @@
+// Native test runtime: runs instructions through the program entrypoint with
+// the system program, SPL Token and (with `test-amm`) the test AMM behind the
+// CPI syscall stub, so tests can exercise handlers end to end without a
+// validator.
+//
+// Account memory mirrors the layout the loader serializes: realloc writes the
+// new length into the 8 bytes before the data and reads the original length
+// from the 4 bytes before the key, so both are leaked with that padding.
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::sync::Once;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE, SUCCESS};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::program_stubs::{self, SyscallStubs};
+use anchor_lang::solana_program::program_utils::limited_deserialize;
+use anchor_lang::solana_program::system_instruction::{SystemError, SystemInstruction};
+use anchor_lang::system_program;
+use necro_migrate::Migration;
+
+use super::TestAccount;
+
+thread_local! {
+    static NOW: Cell<i64> = const { Cell::new(0) };
+    /// Programs currently executing, innermost last; used to derive PDA signers
+    static CALLERS: RefCell<Vec<Pubkey>> = const { RefCell::new(Vec::new()) };
+}
+
+struct RuntimeStubs;
+
+impl SyscallStubs for RuntimeStubs {
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let clock = Clock { unix_timestamp: NOW.with(Cell::get), ..Clock::default() };
+        unsafe { *(var_addr as *mut Clock) = clock };
+        SUCCESS
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        SUCCESS
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let caller = CALLERS.with(|callers| *callers.borrow().last().unwrap());
+        let signers = signers_seeds
+            .iter()
+            .map(|seeds| Pubkey::create_program_address(seeds, &caller))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let mut accounts = Vec::with_capacity(instruction.accounts.len());
+        for meta in &instruction.accounts {
+            let mut info = account_infos
+                .iter()
+                .find(|info| *info.key == meta.pubkey)
+                .ok_or(ProgramError::NotEnoughAccountKeys)?
+                .clone();
+            info.is_signer |= signers.contains(info.key);
+            if meta.is_signer && !info.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            accounts.push(info);
+        }
+
+        if instruction.program_id == system_program::ID {
+            return process_system(&accounts, &instruction.data);
+        }
+        if instruction.program_id == spl_token::ID {
+            return spl_token::processor::Processor::process(&spl_token::ID, &accounts, &instruction.data);
+        }
+        #[cfg(feature = "test-amm")]
+        if instruction.program_id == test_amm::ID {
+            // Anchor entrypoints tie the slice borrow to the account lifetime
+            let accounts = Box::leak(accounts.into_boxed_slice());
+            return with_caller(test_amm::ID, || test_amm::entry(&test_amm::ID, accounts, &instruction.data));
+        }
+        Err(ProgramError::IncorrectProgramId)
+    }
+}
+
+/// Run `f` as `program_id`, so PDAs it signs for derive from that program
+fn with_caller<T>(program_id: Pubkey, f: impl FnOnce() -> T) -> T {
+    CALLERS.with(|callers| callers.borrow_mut().push(program_id));
+    let result = f();
+    CALLERS.with(|callers| callers.borrow_mut().pop());
+    result
+}
+
+fn process_system(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let instruction: SystemInstruction =
+        limited_deserialize(data, 1232).map_err(|_| ProgramError::InvalidInstructionData)?;
+    match instruction {
+        SystemInstruction::CreateAccount { lamports, space, owner } => {
+            if accounts[1].lamports() > 0 {
+                return Err(ProgramError::Custom(SystemError::AccountAlreadyInUse as u32));
+            }
+            allocate(&accounts[1], space)?;
+            transfer(&accounts[0], &accounts[1], lamports)?;
+            accounts[1].assign(&owner);
+            Ok(())
+        }
+        SystemInstruction::Transfer { lamports } => transfer(&accounts[0], &accounts[1], lamports),
+        SystemInstruction::Allocate { space } => allocate(&accounts[0], space),
+        SystemInstruction::Assign { owner } => {
+            accounts[0].assign(&owner);
+            Ok(())
+        }
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+fn allocate(account: &AccountInfo, space: u64) -> ProgramResult {
+    if !account.data_is_empty() || *account.owner != system_program::ID {
+        return Err(ProgramError::Custom(SystemError::AccountAlreadyInUse as u32));
+    }
+    account.realloc(space as usize, true)
+}
+
+fn transfer(from: &AccountInfo, to: &AccountInfo, lamports: u64) -> ProgramResult {
+    let remaining = from
+        .lamports()
+        .checked_sub(lamports)
+        .ok_or(ProgramError::Custom(SystemError::ResultWithNegativeLamports as u32))?;
+    **from.try_borrow_mut_lamports()? = remaining;
+    **to.try_borrow_mut_lamports()? += lamports;
+    Ok(())
+}
+
+/// Key preceded by the original data length, as realloc expects
+#[repr(C)]
+struct KeySlot {
+    original_data_len: u32,
+    key: Pubkey,
+}
+
+fn leak_info(account: &TestAccount, is_signer: bool, is_writable: bool) -> AccountInfo<'static> {
+    let slot = Box::leak(Box::new(KeySlot { original_data_len: account.data.len() as u32, key: account.key }));
+
+    // u64 words keep the length prefix aligned; the tail leaves room to grow
+    let len = account.data.len();
+    let buffer = Box::leak(vec![0u64; 1 + (len + MAX_PERMITTED_DATA_INCREASE).div_ceil(8)].into_boxed_slice());
+    buffer[0] = len as u64;
+    let data = unsafe {
+        let ptr = (buffer.as_mut_ptr() as *mut u8).add(8);
+        std::ptr::copy_nonoverlapping(account.data.as_ptr(), ptr, len);
+        std::slice::from_raw_parts_mut(ptr, len)
+    };
+
+    AccountInfo::new(
+        &slot.key,
+        is_signer,
+        is_writable,
+        Box::leak(Box::new(account.lamports)),
+        data,
+        Box::leak(Box::new(account.owner)),
+        account.executable,
+        0,
+    )
+}
+
+/// Accounts plus a clock. `process` only commits account changes when the
+/// instruction succeeds, like a transaction.
+pub struct Runtime {
+    pub accounts: HashMap<Pubkey, TestAccount>,
+}
+
+impl Runtime {
+    pub fn new() -> Self {
+        static STUBS: Once = Once::new();
+        STUBS.call_once(|| {
+            program_stubs::set_syscall_stubs(Box::new(RuntimeStubs));
+        });
+        NOW.with(|now| now.set(1_700_000_000));
+
+        let mut runtime = Self { accounts: HashMap::new() };
+        runtime.add(TestAccount::program(system_program::ID));
+        runtime.add(TestAccount::program(spl_token::ID));
+        runtime.add(TestAccount::program(necro_migrate::ID));
+        #[cfg(feature = "test-amm")]
+        runtime.add(TestAccount::program(test_amm::ID));
+        runtime
+    }
+
+    pub fn now(&self) -> i64 {
+        NOW.with(Cell::get)
+    }
+
+    pub fn warp(&mut self, seconds: i64) {
+        NOW.with(|now| now.set(now.get() + seconds));
+    }
+
+    pub fn add(&mut self, account: TestAccount) -> Pubkey {
+        let key = account.key;
+        self.accounts.insert(key, account);
+        key
+    }
+
+    /// Unknown keys are empty system accounts, like on chain
+    pub fn get(&self, key: &Pubkey) -> TestAccount {
+        self.accounts.get(key).cloned().unwrap_or_else(|| TestAccount {
+            lamports: 0,
+            ..TestAccount::new(*key, system_program::ID, vec![])
+        })
+    }
+
+    pub fn account<T: AccountDeserialize>(&self, key: &Pubkey) -> T {
+        T::try_deserialize(&mut self.get(key).data.as_slice()).unwrap()
+    }
+
+    pub fn token_amount(&self, key: &Pubkey) -> u64 {
+        spl_token::state::Account::unpack(&self.get(key).data).unwrap().amount
+    }
+
+    /// Store `migration` at a fresh key, with its mint and vault owned by the
+    /// migration authority PDA
+    pub fn add_migration(&mut self, migration: Migration) -> Pubkey {
+        let key = Pubkey::new_unique();
+        let (authority, authority_bump) = authority_pda(&key);
+
+        let mut mint = TestAccount::mint(migration.mint, authority);
+        let mut state = spl_token::state::Mint::unpack(&mint.data).unwrap();
+        state.decimals = migration.decimals;
+        state.pack_into_slice(&mut mint.data);
+        self.add(mint);
+
+        self.add(TestAccount { key: migration.token_vault, ..TestAccount::token_account(migration.mint, authority) });
+        self.add(TestAccount::anchor(key, &Migration { authority_bump, ..migration }));
+        key
+    }
+
+    /// Empty token account of `mint` owned by `owner`
+    pub fn add_token_account(&mut self, mint: Pubkey, owner: Pubkey) -> Pubkey {
+        let mut account = TestAccount::token_account(mint, owner);
+        let mut state = spl_token::state::Account::unpack(&account.data).unwrap();
+        state.amount = 0;
+        state.pack_into_slice(&mut account.data);
+        self.add(account)
+    }
+
+    pub fn process(&mut self, instruction: &Instruction) -> std::result::Result<(), ProgramError> {
+        // One AccountInfo per key; duplicates in the metas share it
+        let mut infos: HashMap<Pubkey, AccountInfo<'static>> = HashMap::new();
+        for meta in &instruction.accounts {
+            let (is_signer, is_writable) = instruction
+                .accounts
+                .iter()
+                .filter(|other| other.pubkey == meta.pubkey)
+                .fold((false, false), |(s, w), other| (s || other.is_signer, w || other.is_writable));
+            infos
+                .entry(meta.pubkey)
+                .or_insert_with(|| leak_info(&self.get(&meta.pubkey), is_signer, is_writable));
+        }
+        let ordered: &'static [AccountInfo<'static>] = Box::leak(
+            instruction.accounts.iter().map(|meta| infos[&meta.pubkey].clone()).collect::<Vec<_>>().into_boxed_slice(),
+        );
+
+        with_caller(instruction.program_id, || {
+            necro_migrate::entry(&instruction.program_id, ordered, &instruction.data)
+        })?;
+
+        for info in infos.values() {
+            let account = TestAccount {
+                key: *info.key,
+                owner: *info.owner,
+                lamports: info.lamports(),
+                data: info.data.borrow().to_vec(),
+                is_signer: self.get(info.key).is_signer,
+                executable: info.executable,
+            };
+            self.accounts.insert(account.key, account);
+        }
+        Ok(())
+    }
+}
+
+impl Default for Runtime {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn authority_pda(migration: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"authority", migration.as_ref()], &necro_migrate::ID)
+}
+
+/// The error an instruction fails with once it leaves the program
+pub fn program_error(error: impl Into<anchor_lang::error::Error>) -> ProgramError {
+    error.into().into()
+}
@@ -0,0 +1,248 @@
+#![cfg(all(test, feature = "test-amm", not(feature = "mainnet")))]
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::system_instruction::SystemError;
+use anchor_lang::{system_program, InstructionData};
+use necro_migrate::amm::{adapter_for, AmmAdapter, DepositAccounts, TestAmmAdapter};
+use necro_migrate::{DAOLiquidity, ErrorCode};
+
+mod common;
+use common::runtime::Runtime;
+use common::TestAccount;
+
+fn dao_liquidity(lp_percentage: u8, total_reserved: u64) -> DAOLiquidity {
+    DAOLiquidity {
+        migration: Pubkey::new_unique(),
+        dao_treasury: Pubkey::new_unique(),
+        lp_percentage,
+        pool_initialized: false,
+        total_reserved,
+        bump: 255,
+        pending_treasury: Pubkey::default(),
+        amm_program: Pubkey::default(),
+        lp_mint: Pubkey::default(),
+    }
+}
+
+fn pool_data(mint_a: Pubkey, mint_b: Pubkey, lp_mint: Pubkey) -> Vec<u8> {
+    let pool = test_amm::Pool {
+        mint_a,
+        mint_b,
+        vault_a: Pubkey::new_unique(),
+        vault_b: Pubkey::new_unique(),
+        lp_mint,
+        bump: 255,
+    };
+    let mut data = Vec::new();
+    pool.try_serialize(&mut data).unwrap();
+    data
+}
+
+/// Build the test AMM deposit for a pool with the given mint order
+fn build_deposit(pool_mints: impl FnOnce(Pubkey, Pubkey) -> (Pubkey, Pubkey), pool_owner: Pubkey) -> Result<(Vec<Pubkey>, Vec<u8>)> {
+    let token = spl_token::ID;
+    let mut depositor = TestAccount::new(Pubkey::new_unique(), necro_migrate::ID, vec![]);
+    let mut token_mint = TestAccount::new(Pubkey::new_unique(), token, vec![]);
+    let mut quote_mint = TestAccount::new(Pubkey::new_unique(), token, vec![]);
+    let mut token_source = TestAccount::new(Pubkey::new_unique(), token, vec![]);
+    let mut quote_source = TestAccount::new(Pubkey::new_unique(), token, vec![]);
+    let mut lp_mint = TestAccount::new(Pubkey::new_unique(), token, vec![]);
+    let mut lp_destination = TestAccount::new(Pubkey::new_unique(), token, vec![]);
+    let mut token_program = TestAccount::program(token);
+
+    let (mint_a, mint_b) = pool_mints(token_mint.key, quote_mint.key);
+    let mut pool = TestAccount::new(Pubkey::new_unique(), pool_owner, pool_data(mint_a, mint_b, lp_mint.key));
+    let mut vault_a = TestAccount::new(Pubkey::new_unique(), token, vec![]);
+    let mut vault_b = TestAccount::new(Pubkey::new_unique(), token, vec![]);
+
+    let pool_accounts = [pool.info(), vault_a.info(), vault_b.info()];
+    let accounts = DepositAccounts {
+        depositor: &depositor.info(),
+        token_mint: &token_mint.info(),
+        quote_mint: &quote_mint.info(),
+        token_source: &token_source.info(),
+        quote_source: &quote_source.info(),
+        lp_mint: &lp_mint.info(),
+        lp_destination: &lp_destination.info(),
+        token_program: &token_program.info(),
+        pool_accounts: &pool_accounts,
+    };
+
+    let ix = TestAmmAdapter.deposit_instruction(&accounts, 1_000, 50, 1)?;
+    assert_eq!(ix.program_id, test_amm::ID);
+    assert!(ix.accounts[0].is_signer);
+    Ok((ix.accounts.iter().map(|meta| meta.pubkey).collect(), ix.data))
+}
+
+fn add_liquidity_args(data: &[u8]) -> (u64, u64, u64) {
+    let args = &data[8..];
+    (
+        u64::from_le_bytes(args[0..8].try_into().unwrap()),
+        u64::from_le_bytes(args[8..16].try_into().unwrap()),
+        u64::from_le_bytes(args[16..24].try_into().unwrap()),
+    )
+}
+
+#[test]
+fn test_reserve_target_follows_lp_percentage() {
+    // 10% of 1M supply
+    assert_eq!(dao_liquidity(10, 0).reserve_target(1_000_000), 100_000);
+    assert!(!dao_liquidity(10, 99_999).reserve_reached(1_000_000));
+    assert!(dao_liquidity(10, 100_000).reserve_reached(1_000_000));
+
+    // No overflow at the top of the range
+    assert_eq!(dao_liquidity(20, 0).reserve_target(u64::MAX), u64::MAX / 5);
+
+    // An empty reserve never seeds a pool, even against a tiny supply
+    assert!(!dao_liquidity(1, 0).reserve_reached(10));
+
+    println!("✓ LP reserve threshold");
+}
+
+#[test]
+fn test_claim_carve_out_rounds_in_users_favour() {
+    let dao = dao_liquidity(10, 0);
+
+    assert_eq!(dao.lp_share(1_000), 100);
+    // 10% of 999 is 99.9; the fraction stays with the claimer
+    assert_eq!(dao.lp_share(999), 99);
+    assert_eq!(dao.lp_share(9), 0);
+    assert_eq!(dao_liquidity(20, 0).lp_share(u64::MAX), u64::MAX / 5);
+
+    for amount in [1, 7, 999, 1_000, 123_456_789] {
+        let lp = dao.lp_share(amount);
+        assert!(lp <= amount);
+        assert_eq!(lp + (amount - lp), amount);
+    }
+
+    println!("✓ Claim LP carve-out");
+}
+
+#[test]
+fn test_carve_outs_accumulate_toward_seeding() {
+    let mut dao = dao_liquidity(10, 0);
+    let total_supply = 10_000;
+
+    // Claims totalling the whole supply reserve exactly lp_percentage of it
+    for _ in 0..10 {
+        dao.total_reserved += dao.lp_share(1_000);
+    }
+    assert_eq!(dao.total_reserved, 1_000);
+    assert!(dao.reserve_reached(total_supply));
+}
+
+#[test]
+fn test_unknown_amm_rejected() {
+    assert_eq!(adapter_for(&Pubkey::new_unique()).err().unwrap(), ErrorCode::UnsupportedAmm.into());
+    assert_eq!(adapter_for(&test_amm::ID).ok().unwrap().program_id(), test_amm::ID);
+}
+
+#[test]
+fn test_deposit_orders_sides_by_pool_mints() {
+    // Migrated token is side A
+    let (keys, data) = build_deposit(|token, quote| (token, quote), test_amm::ID).unwrap();
+    assert_eq!(add_liquidity_args(&data), (1_000, 50, 1));
+    assert_eq!(keys.len(), 11);
+
+    // Migrated token is side B: amounts swap with it
+    let (swapped_keys, data) = build_deposit(|token, quote| (quote, token), test_amm::ID).unwrap();
+    assert_eq!(add_liquidity_args(&data), (50, 1_000, 1));
+    assert_eq!(swapped_keys.len(), 11);
+
+    println!("✓ Test AMM deposit instruction");
+}
+
+#[test]
+fn test_deposit_rejects_foreign_pool() {
+    let wrong_mints = build_deposit(|_, quote| (Pubkey::new_unique(), quote), test_amm::ID);
+    assert_eq!(wrong_mints.unwrap_err(), ErrorCode::InvalidAmmAccounts.into());
+
+    let wrong_owner = build_deposit(|token, quote| (token, quote), Pubkey::new_unique());
+    assert_eq!(wrong_owner.unwrap_err(), ErrorCode::InvalidAmmAccounts.into());
+}
+
+#[test]
+fn test_dao_pool_can_only_be_seeded_once() {
+    let mut runtime = Runtime::new();
+    let state = common::migration();
+    let mint = state.mint;
+    let total_supply = state.total_supply;
+    let migration = runtime.add_migration(state);
+
+    let dao_treasury = runtime.add(TestAccount::signer(Pubkey::new_unique()));
+    let (dao_liquidity, bump) =
+        Pubkey::find_program_address(&[b"dao_liquidity", migration.as_ref()], &necro_migrate::ID);
+    let reserved = total_supply / 10;
+    runtime.add(TestAccount::anchor(
+        dao_liquidity,
+        &DAOLiquidity { migration, dao_treasury, bump, ..self::dao_liquidity(10, reserved) },
+    ));
+    let dao_vault = runtime.add(TestAccount::token_account(mint, dao_liquidity));
+    set_token_amount(&mut runtime, dao_vault, reserved);
+
+    let quote_mint = runtime.add(TestAccount::mint(Pubkey::new_unique(), Pubkey::new_unique()));
+    let treasury_quote_account = runtime.add(TestAccount::token_account(quote_mint, dao_treasury));
+    let quote_vault = runtime.add_token_account(quote_mint, dao_liquidity);
+
+    // Test AMM pool with the migrated token as side A, as `initialize_pool` leaves it
+    let (pool, pool_bump) =
+        Pubkey::find_program_address(&[b"pool", mint.as_ref(), quote_mint.as_ref()], &test_amm::ID);
+    let vault_a = runtime.add_token_account(mint, pool);
+    let vault_b = runtime.add_token_account(quote_mint, pool);
+    let mut lp_mint = TestAccount::mint(Pubkey::new_unique(), pool);
+    let mut lp_state = spl_token::state::Mint::unpack(&lp_mint.data).unwrap();
+    lp_state.supply = 0;
+    lp_state.pack_into_slice(&mut lp_mint.data);
+    let lp_mint = runtime.add(lp_mint);
+    let pool_state = test_amm::Pool { mint_a: mint, mint_b: quote_mint, vault_a, vault_b, lp_mint, bump: pool_bump };
+    runtime.add(TestAccount { owner: test_amm::ID, ..TestAccount::anchor(pool, &pool_state) });
+
+    let (lp_lock, _) = Pubkey::find_program_address(&[b"lp_lock", dao_liquidity.as_ref()], &necro_migrate::ID);
+    let mut accounts = necro_migrate::accounts::SeedDAOPool {
+        dao_treasury,
+        migration,
+        dao_liquidity,
+        mint,
+        quote_mint,
+        dao_vault,
+        treasury_quote_account,
+        quote_vault,
+        lp_mint,
+        lp_lock,
+        amm_program: test_amm::ID,
+        token_program: spl_token::ID,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None);
+    accounts.extend([AccountMeta::new_readonly(pool, false), AccountMeta::new(vault_a, false), AccountMeta::new(vault_b, false)]);
+    let seed = Instruction {
+        program_id: necro_migrate::ID,
+        accounts,
+        data: necro_migrate::instruction::SeedDaoPool { quote_amount: 400, min_lp: 1 }.data(),
+    };
+    runtime.process(&seed).unwrap();
+
+    // sqrt(100 * 400) LP tokens, locked under the DAOLiquidity PDA
+    assert_eq!(runtime.token_amount(&lp_lock), 200);
+    assert_eq!((runtime.token_amount(&vault_a), runtime.token_amount(&vault_b)), (reserved, 400));
+    let dao: DAOLiquidity = runtime.account(&dao_liquidity);
+    assert!(dao.pool_initialized);
+    assert_eq!((dao.amm_program, dao.lp_mint), (test_amm::ID, lp_mint));
+
+    // The lp_lock PDA already exists, so the second seed fails before the handler runs
+    let err = runtime.process(&seed).unwrap_err();
+    assert_eq!(err, ProgramError::Custom(SystemError::AccountAlreadyInUse as u32));
+    assert_eq!(runtime.token_amount(&lp_lock), 200);
+
+    println!("✓ DAO pool seeds once");
+}
+
+fn set_token_amount(runtime: &mut Runtime, account: Pubkey, amount: u64) {
+    let mut account = runtime.get(&account);
+    let mut state = spl_token::state::Account::unpack(&account.data).unwrap();
+    state.amount = amount;
+    state.pack_into_slice(&mut account.data);
+    runtime.add(account);
+}
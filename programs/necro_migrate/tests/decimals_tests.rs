@@ -0,0 +1,135 @@
+#![cfg(test)]
+
+use necro_migrate::decimals::{rescale, validate, MAX_DECIMALS_DIFF};
+use necro_migrate::{ErrorCode, Migration};
+use proptest::prelude::*;
+
+mod common;
+
+fn migration(total_supply: u64, decimals: u8, source_decimals: u8) -> Migration {
+    Migration { source_chain: 2, total_supply, decimals, source_decimals, ..common::migration() }
+}
+
+proptest! {
+    #[test]
+    fn prop_same_decimals_is_identity(amount: u64, decimals in 0u8..=32) {
+        prop_assert_eq!(rescale(amount, decimals, decimals).unwrap(), (amount, 0));
+    }
+
+    #[test]
+    fn prop_scale_down_conserves_amount(amount: u64, decimals in 0u8..=18, diff in 0u8..=20) {
+        let source_decimals = decimals.saturating_add(diff);
+        let (local, dust) = rescale(amount, source_decimals, decimals).unwrap();
+        let factor = 10u128.pow((source_decimals - decimals) as u32);
+
+        prop_assert!((dust as u128) < factor);
+        prop_assert_eq!(local as u128 * factor + dust as u128, amount as u128);
+    }
+
+    #[test]
+    fn prop_scale_down_is_monotonic(a: u64, b: u64, diff in 1u8..=20) {
+        let (lo, hi) = (a.min(b), a.max(b));
+        let (local_lo, _) = rescale(lo, 6 + diff, 6).unwrap();
+        let (local_hi, _) = rescale(hi, 6 + diff, 6).unwrap();
+        prop_assert!(local_lo <= local_hi);
+    }
+
+    #[test]
+    fn prop_scale_up_is_exact_or_fails(amount: u64, source_decimals in 0u8..=18, diff in 1u8..=25) {
+        let decimals = source_decimals + diff;
+        let expected = 10u128
+            .checked_pow(diff as u32)
+            .and_then(|factor| factor.checked_mul(amount as u128))
+            .filter(|local| *local <= u64::MAX as u128);
+
+        match (rescale(amount, source_decimals, decimals), expected) {
+            (Ok((local, dust)), Some(expected)) => {
+                prop_assert_eq!(local as u128, expected);
+                prop_assert_eq!(dust, 0);
+            }
+            (Err(err), None) => prop_assert_eq!(err, ErrorCode::DecimalOverflow.into()),
+            (result, expected) => prop_assert!(false, "{:?} vs {:?}", result, expected),
+        }
+    }
+
+    #[test]
+    fn prop_settle_claims_track_dust(amounts in prop::collection::vec(1_000u64..=u32::MAX as u64, 1..20)) {
+        let mut migration = migration(u64::MAX, 6, 9);
+        let mut paid = 0u128;
+        for amount in &amounts {
+            paid += migration.settle_claim(*amount).unwrap() as u128;
+        }
+
+        let total: u128 = amounts.iter().map(|amount| *amount as u128).sum();
+        prop_assert_eq!(migration.migrated_amount as u128, paid);
+        prop_assert_eq!(paid * 1_000 + migration.dust, total);
+    }
+}
+
+#[test]
+fn test_rescale_rounds_down() {
+    // 1.234567891 source tokens (9 decimals) to 6 decimals
+    assert_eq!(rescale(1_234_567_891, 9, 6).unwrap(), (1_234_567, 891));
+    assert_eq!(rescale(999, 9, 6).unwrap(), (0, 999));
+    println!("✓ Rescaling rounds down and keeps the remainder as dust");
+}
+
+#[test]
+fn test_rescale_huge_factor_is_all_dust() {
+    assert_eq!(rescale(u64::MAX, 255, 0).unwrap(), (0, u64::MAX));
+    println!("✓ Factors beyond u128 turn the whole amount into dust");
+}
+
+#[test]
+fn test_rescale_up_overflow() {
+    let err = rescale(u64::MAX, 6, 9).unwrap_err();
+    assert_eq!(err, ErrorCode::DecimalOverflow.into());
+    assert_eq!(rescale(0, 0, 255).unwrap(), (0, 0));
+    println!("✓ Scaling up fails instead of wrapping");
+}
+
+#[test]
+fn test_settle_claim_uses_local_units_for_cap() {
+    // 18-decimal snapshot into a 6-decimal mint with a supply of 10 tokens
+    let mut migration = migration(10_000_000, 6, 18);
+
+    let paid = migration.settle_claim(4_000_000_000_000_000_001).unwrap();
+    assert_eq!(paid, 4_000_000);
+    assert_eq!(migration.migrated_amount, 4_000_000);
+    assert_eq!(migration.dust, 1);
+
+    let err = migration.settle_claim(7_000_000_000_000_000_000).unwrap_err();
+    assert_eq!(err, ErrorCode::SupplyCapExceeded.into());
+    assert_eq!(migration.migrated_amount, 4_000_000);
+    assert_eq!(migration.dust, 1);
+    println!("✓ Supply cap is enforced on rescaled amounts");
+}
+
+#[test]
+fn test_settle_claim_rejects_sub_unit_amounts() {
+    let mut migration = migration(10_000_000, 6, 9);
+
+    // 0.000000999 source tokens would round to 0 and burn the leaf
+    for amount in [0, 999] {
+        let err = migration.settle_claim(amount).unwrap_err();
+        assert_eq!(err, ErrorCode::RescaledToZero.into());
+    }
+    assert_eq!(migration.migrated_amount, 0);
+    assert_eq!(migration.dust, 0);
+
+    assert_eq!(migration.settle_claim(1_000).unwrap(), 1);
+    println!("✓ Claims worth less than one mint unit are rejected");
+}
+
+#[test]
+fn test_decimals_gap_is_bounded() {
+    validate(18, 0).unwrap();
+    validate(0, MAX_DECIMALS_DIFF).unwrap();
+    validate(9, 9).unwrap();
+
+    for (source_decimals, decimals) in [(19, 0), (0, 19), (255, 6)] {
+        let err = validate(source_decimals, decimals).unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidDecimals.into());
+    }
+    println!("✓ Decimals that no claim could use are rejected at init");
+}
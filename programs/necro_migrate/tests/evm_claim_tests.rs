@@ -0,0 +1,107 @@
+#![cfg(test)]
+
+use libsecp256k1::{sign, Message, PublicKey, SecretKey};
+use necro_migrate::evm::{
+    address_from_pubkey, claim_message, eth_signed_message_hash, recover_address, verify_claim_signature,
+};
+use necro_migrate::{merkle, ErrorCode};
+use solana_sdk::pubkey::Pubkey;
+
+fn evm_holder(seed: u8) -> (SecretKey, [u8; 20]) {
+    let secret = SecretKey::parse(&[seed; 32]).unwrap();
+    let public = PublicKey::from_secret_key(&secret).serialize();
+    let address = address_from_pubkey(public[1..].try_into().unwrap());
+    (secret, address)
+}
+
+/// Sign like `personal_sign`: returns (r || s, v) with v in Ethereum's 27/28 form
+fn personal_sign(secret: &SecretKey, message: &[u8]) -> ([u8; 64], u8) {
+    let hash = eth_signed_message_hash(message);
+    let (signature, recovery_id) = sign(&Message::parse(&hash), secret);
+    (signature.serialize(), recovery_id.serialize() + 27)
+}
+
+#[test]
+fn test_known_address_derivation() {
+    // Private key 0x01 is the well-known address 0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf
+    let mut key = [0u8; 32];
+    key[31] = 1;
+    let public = PublicKey::from_secret_key(&SecretKey::parse(&key).unwrap()).serialize();
+    let address = address_from_pubkey(public[1..].try_into().unwrap());
+
+    assert_eq!(
+        address,
+        [
+            0x7e, 0x5f, 0x45, 0x52, 0x09, 0x1a, 0x69, 0x12, 0x5d, 0x5d, 0xfc, 0xb7, 0xb8, 0xc2, 0x65, 0x90,
+            0x29, 0x39, 0x5b, 0xdf
+        ]
+    );
+}
+
+#[test]
+fn test_eip191_prefix() {
+    let message = claim_message(&Pubkey::new_unique(), &Pubkey::new_unique(), 1, 0);
+    let expected = solana_program::keccak::hashv(&[b"\x19Ethereum Signed Message:\n76", &message]);
+    assert_eq!(eth_signed_message_hash(&message), expected.to_bytes());
+}
+
+#[test]
+fn test_evm_holder_signature_binds_recipient() {
+    let (secret, evm_address) = evm_holder(7);
+    let migration = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+
+    let message = claim_message(&migration, &recipient, 2_500, 4);
+    let (signature, v) = personal_sign(&secret, &message);
+
+    verify_claim_signature(&evm_address, &migration, &recipient, 2_500, 4, &signature, v).unwrap();
+    // 0/1 recovery ids are accepted too
+    verify_claim_signature(&evm_address, &migration, &recipient, 2_500, 4, &signature, v - 27).unwrap();
+
+    // Same signature can't redirect tokens to someone else or change the amount
+    let attacker = Pubkey::new_unique();
+    let err = verify_claim_signature(&evm_address, &migration, &attacker, 2_500, 4, &signature, v).unwrap_err();
+    assert_eq!(err, ErrorCode::InvalidSignature.into());
+    let err = verify_claim_signature(&evm_address, &migration, &recipient, 25_000, 4, &signature, v).unwrap_err();
+    assert_eq!(err, ErrorCode::InvalidSignature.into());
+
+    println!("✓ EVM holder signature verified via secp256k1_recover");
+}
+
+#[test]
+fn test_rejects_other_evm_signer() {
+    let (_, evm_address) = evm_holder(7);
+    let (other, _) = evm_holder(8);
+    let migration = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+
+    let (signature, v) = personal_sign(&other, &claim_message(&migration, &recipient, 100, 0));
+    let err = verify_claim_signature(&evm_address, &migration, &recipient, 100, 0, &signature, v).unwrap_err();
+    assert_eq!(err, ErrorCode::InvalidSignature.into());
+}
+
+#[test]
+fn test_rejects_high_s_and_bad_recovery_id() {
+    let (secret, _) = evm_holder(9);
+    let hash = eth_signed_message_hash(b"necrobridge");
+    let (signature, v) = personal_sign(&secret, b"necrobridge");
+
+    let err = recover_address(&hash, &signature, 3).unwrap_err();
+    assert_eq!(err, ErrorCode::InvalidSignature.into());
+
+    let mut high_s = signature;
+    high_s[32] = 0xff;
+    let err = recover_address(&hash, &high_s, v).unwrap_err();
+    assert_eq!(err, ErrorCode::InvalidSignature.into());
+}
+
+#[test]
+fn test_evm_leaf_differs_from_solana_leaf() {
+    let (_, evm_address) = evm_holder(7);
+    let leaf = merkle::evm_leaf_hash(&evm_address, 1_000, 0);
+
+    let sibling = [5u8; 32];
+    let root = merkle::compute_root(leaf, &[sibling]);
+    assert!(merkle::verify(leaf, &[sibling], &root));
+    assert!(!merkle::verify(merkle::evm_leaf_hash(&evm_address, 1_001, 0), &[sibling], &root));
+}
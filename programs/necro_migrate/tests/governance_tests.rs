@@ -0,0 +1,611 @@
+#![cfg(test)]
+
+use anchor_lang::prelude::ProgramError;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::system_instruction::SystemError;
+use anchor_lang::{system_program, AnchorSerialize, InstructionData, ToAccountMetas};
+use common::runtime::{self, Runtime};
+use common::TestAccount;
+use necro_migrate::governance::{
+    isqrt, validate_actions, vote_weight, ProposalAction, ProposalStatus, MAX_PROPOSAL_ACTIONS,
+};
+use necro_migrate::timelock::{OperationKind, OperationStatus};
+use necro_migrate::{DAOLiquidity, ErrorCode, Governance, Migration, Proposal, QueuedOperation, VoteEscrow};
+use solana_sdk::pubkey::Pubkey;
+
+mod common;
+
+fn proposal(votes_for: u64, votes_against: u64) -> Proposal {
+    Proposal {
+        governance: Pubkey::new_unique(),
+        id: 0,
+        proposer: Pubkey::new_unique(),
+        description_hash: [0u8; 32],
+        votes_for,
+        votes_against,
+        voting_starts: 0,
+        voting_ends: 100,
+        status: ProposalStatus::Active,
+        bump: 255,
+        unique_voters: 0,
+        quorum_voters: 0,
+        quorum_weight: 0,
+        executable_at: 200,
+        actions: Vec::new(),
+    }
+}
+
+fn migration(authority: Pubkey) -> Migration {
+    Migration { paused: true, authority, ..common::migration() }
+}
+
+fn governance(migration: Pubkey) -> Governance {
+    Governance {
+        migration,
+        total_votes: 0,
+        mint: Pubkey::new_unique(),
+        proposal_count: 1,
+        voting_period: 100,
+        bump: 255,
+        quorum_voters: 1,
+        quorum_weight: 1,
+        execution_delay: 0,
+    }
+}
+
+fn dao_liquidity(migration: Pubkey) -> DAOLiquidity {
+    DAOLiquidity {
+        migration,
+        dao_treasury: Pubkey::new_unique(),
+        lp_percentage: 10,
+        pool_initialized: false,
+        total_reserved: 0,
+        bump: 255,
+        pending_treasury: Pubkey::new_unique(),
+        amm_program: Pubkey::default(),
+        lp_mint: Pubkey::default(),
+    }
+}
+
+#[test]
+fn test_isqrt_matches_definition() {
+    for n in 0..10_000u64 {
+        let root = isqrt(n);
+        assert!(root * root <= n && (root + 1) * (root + 1) > n, "isqrt({})", n);
+    }
+
+    for n in [u64::MAX, u64::MAX - 1, 1 << 62, (1 << 32) * ((1 << 32) - 1), 999_999_999_999] {
+        let root = isqrt(n) as u128;
+        assert!(root * root <= n as u128 && (root + 1) * (root + 1) > n as u128, "isqrt({})", n);
+    }
+    assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+
+    println!("✓ Integer square root");
+}
+
+#[test]
+fn test_quadratic_weight_dampens_whales() {
+    let holder = vote_weight(10_000);
+    let whale = vote_weight(1_000_000);
+
+    assert_eq!(holder, 100);
+    assert_eq!(whale, 1_000);
+    // 100x the stake buys only 10x the weight
+    assert_eq!(whale / holder, 10);
+
+    // Ten holders with 10k each outvote one whale with 1M
+    assert!(10 * holder >= whale);
+}
+
+#[test]
+fn test_tally_outcome() {
+    assert_eq!(proposal(10, 3).outcome(), ProposalStatus::Passed);
+    assert_eq!(proposal(3, 10).outcome(), ProposalStatus::Rejected);
+    assert_eq!(proposal(5, 5).outcome(), ProposalStatus::Rejected);
+    assert_eq!(proposal(0, 0).outcome(), ProposalStatus::Rejected);
+}
+
+#[test]
+fn test_quorum_blocks_whale_only_proposals() {
+    // One whale with overwhelming weight, but only 10 wallets against a 50 wallet quorum
+    let mut whale_vote = proposal(10_000, 0);
+    whale_vote.unique_voters = 10;
+    whale_vote.quorum_voters = 50;
+    whale_vote.quorum_weight = 1_000;
+
+    assert_eq!(whale_vote.check_quorum().unwrap_err(), ErrorCode::QuorumVotersNotReached.into());
+    assert_eq!(whale_vote.outcome(), ProposalStatus::Rejected);
+
+    // Enough wallets, but not enough total weight
+    let mut thin_vote = proposal(400, 100);
+    thin_vote.unique_voters = 60;
+    thin_vote.quorum_voters = 50;
+    thin_vote.quorum_weight = 1_000;
+
+    assert_eq!(thin_vote.check_quorum().unwrap_err(), ErrorCode::QuorumWeightNotReached.into());
+    assert_eq!(thin_vote.outcome(), ProposalStatus::Rejected);
+
+    thin_vote.votes_for = 900;
+    thin_vote.check_quorum().unwrap();
+    assert_eq!(thin_vote.outcome(), ProposalStatus::Passed);
+
+    println!("✓ Quorum enforced on unique wallets and weight");
+}
+
+#[test]
+fn test_second_vote_from_same_wallet_is_rejected() {
+    let mut runtime = Runtime::new();
+    let now = runtime.now();
+
+    let governance = runtime.add(TestAccount::anchor(Pubkey::new_unique(), &governance(Pubkey::new_unique())));
+    let proposal = runtime.add(TestAccount::anchor(
+        Pubkey::new_unique(),
+        &Proposal { governance, voting_starts: now - 10, voting_ends: now + 100, ..proposal(0, 0) },
+    ));
+    let voter = runtime.add(TestAccount::signer(Pubkey::new_unique()));
+    let (vote_escrow, bump) = Pubkey::find_program_address(
+        &[b"vote_escrow", governance.as_ref(), voter.as_ref()],
+        &necro_migrate::ID,
+    );
+    runtime.add(TestAccount::anchor(
+        vote_escrow,
+        &VoteEscrow { governance, owner: voter, amount: 10_000, locked_at: now - 100, bump, ..escrow() },
+    ));
+    let (vote_record, _) =
+        Pubkey::find_program_address(&[b"vote", proposal.as_ref(), voter.as_ref()], &necro_migrate::ID);
+
+    let cast_vote = |support| Instruction {
+        program_id: necro_migrate::ID,
+        accounts: necro_migrate::accounts::CastVote {
+            voter,
+            governance,
+            proposal,
+            vote_record,
+            vote_escrow,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: necro_migrate::instruction::CastVote { support }.data(),
+    };
+
+    runtime.process(&cast_vote(true)).unwrap();
+
+    // The voter's record for this proposal already exists, so its `init` fails
+    let err = runtime.process(&cast_vote(false)).unwrap_err();
+    assert_eq!(err, ProgramError::Custom(SystemError::AccountAlreadyInUse as u32));
+
+    let state: Proposal = runtime.account(&proposal);
+    assert_eq!((state.votes_for, state.votes_against, state.unique_voters), (vote_weight(10_000), 0, 1));
+
+    println!("✓ One vote per wallet per proposal");
+}
+
+/// Migration administered by its governance PDA, as after `accept_governance_admin`
+fn governed_migration(runtime: &mut Runtime, state: Migration, execution_delay: i64) -> (Pubkey, Pubkey) {
+    let migration = runtime.add_migration(state);
+    let (governance, bump) =
+        Pubkey::find_program_address(&[b"governance", migration.as_ref()], &necro_migrate::ID);
+    let state: Migration = runtime.account(&migration);
+    runtime.add(TestAccount::anchor(migration, &Migration { authority: governance, ..state }));
+    runtime.add(TestAccount::anchor(
+        governance,
+        &Governance { bump, execution_delay, ..self::governance(migration) },
+    ));
+    (migration, governance)
+}
+
+fn passed_proposal(runtime: &mut Runtime, governance: Pubkey, actions: Vec<ProposalAction>) -> Pubkey {
+    let proposal = Proposal {
+        governance,
+        status: ProposalStatus::Passed,
+        executable_at: runtime.now(),
+        actions,
+        ..proposal(10, 0)
+    };
+    runtime.add(TestAccount::anchor(Pubkey::new_unique(), &proposal))
+}
+
+/// Accounts for `execute_proposal` with every optional account left out
+fn execute_accounts(migration: Pubkey, governance: Pubkey, proposal: Pubkey) -> necro_migrate::accounts::ExecuteProposal {
+    necro_migrate::accounts::ExecuteProposal {
+        migration,
+        governance,
+        proposal,
+        dao_liquidity: None,
+        payer: None,
+        snapshot_epoch: None,
+        queued_operation: None,
+        system_program: None,
+    }
+}
+
+fn execute_proposal_ix(accounts: necro_migrate::accounts::ExecuteProposal) -> Instruction {
+    Instruction {
+        program_id: necro_migrate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: necro_migrate::instruction::ExecuteProposal {}.data(),
+    }
+}
+
+fn escrow() -> VoteEscrow {
+    VoteEscrow {
+        governance: Pubkey::new_unique(),
+        owner: Pubkey::new_unique(),
+        amount: 0,
+        locked_at: 0,
+        bump: 255,
+        prior_amount: 0,
+        prior_locked_at: 0,
+    }
+}
+
+#[test]
+fn test_only_locks_before_checkpoint_count() {
+    let proposal_created = 1_000;
+
+    let mut early = escrow();
+    early.lock(10_000, 900).unwrap();
+    assert_eq!(early.voting_power_at(proposal_created), 10_000);
+
+    // Tokens locked after (or in the same second as) proposal creation don't count
+    let mut late = escrow();
+    late.lock(10_000, proposal_created).unwrap();
+    assert_eq!(late.voting_power_at(proposal_created), 0);
+    assert_eq!(late.voting_power_at(proposal_created + 1), 10_000);
+
+    println!("✓ Voting power checkpointed at proposal creation");
+}
+
+#[test]
+fn test_moving_tokens_mid_vote_does_not_double_count() {
+    let proposal_created = 1_000;
+
+    // Alice votes with a pre-proposal lock, then withdraws and hands the tokens to Bob
+    let mut alice = escrow();
+    alice.lock(5_000, 500).unwrap();
+    let alice_power = alice.voting_power_at(proposal_created);
+    alice.unlock(5_000).unwrap();
+    assert_eq!(alice.voting_power_at(proposal_created), 0);
+
+    // Bob already had an escrow from before the proposal; only his own balance counts
+    let mut bob = escrow();
+    bob.lock(100, 600).unwrap();
+    assert_eq!(bob.voting_power_at(proposal_created), 100);
+    bob.lock(5_000, 1_200).unwrap();
+    assert_eq!(bob.voting_power_at(proposal_created), 100);
+
+    assert_eq!(alice_power + bob.voting_power_at(proposal_created), 5_100);
+}
+
+#[test]
+fn test_top_up_keeps_power_earned_before_checkpoint() {
+    let proposal_created = 1_000;
+
+    let mut escrow = escrow();
+    escrow.lock(400, 500).unwrap();
+    escrow.lock(100, 500).unwrap();
+    escrow.lock(2_000, 1_100).unwrap();
+    assert_eq!(escrow.voting_power_at(proposal_created), 500);
+    assert_eq!(escrow.voting_power_at(1_101), 2_500);
+
+    // A second top-up must not let the first one count for the old proposal
+    escrow.lock(1, 1_200).unwrap();
+    assert_eq!(escrow.voting_power_at(proposal_created), 0);
+    assert_eq!(escrow.voting_power_at(1_150), 2_500);
+
+    // Unlocking comes off the earned balance as well
+    let mut escrow = self::escrow();
+    escrow.lock(400, 500).unwrap();
+    escrow.lock(100, 1_100).unwrap();
+    escrow.unlock(300).unwrap();
+    assert_eq!(escrow.voting_power_at(proposal_created), 200);
+    escrow.unlock(200).unwrap();
+    assert_eq!(escrow.voting_power_at(proposal_created), 0);
+    assert_eq!(escrow.unlock(1).unwrap_err(), ErrorCode::InvalidAmount.into());
+
+    println!("✓ Top-ups keep voting power locked before the checkpoint");
+}
+
+#[test]
+fn test_proposal_executes_only_after_passing_and_timelock() {
+    let mut passed = proposal(10, 3);
+
+    // Still active: tally has not run yet
+    assert_eq!(passed.check_executable(500).unwrap_err(), ErrorCode::ProposalNotPassed.into());
+
+    passed.status = passed.outcome();
+    assert_eq!(passed.check_executable(199).unwrap_err(), ErrorCode::TimelockNotExpired.into());
+    passed.check_executable(200).unwrap();
+
+    let mut rejected = proposal(3, 10);
+    rejected.status = rejected.outcome();
+    assert_eq!(rejected.check_executable(500).unwrap_err(), ErrorCode::ProposalNotPassed.into());
+
+    // Executing flips the status, so a second execution fails
+    passed.status = ProposalStatus::Executed;
+    assert_eq!(passed.check_executable(500).unwrap_err(), ErrorCode::ProposalNotPassed.into());
+
+    println!("✓ Execution gated on pass + timelock");
+}
+
+#[test]
+fn test_actions_apply_to_migration() {
+    let governance = Pubkey::new_unique();
+    let mut migration = migration(governance);
+    let mut dao_liq = dao_liquidity(Pubkey::new_unique());
+    let new_treasury = Pubkey::new_unique();
+
+    let actions = [
+        ProposalAction::SetSnapshotRoot { root: [9u8; 32], manifest_hash: [8u8; 32] },
+        ProposalAction::Unpause,
+        ProposalAction::SetDaoTreasury { treasury: new_treasury },
+        ProposalAction::SetPoolInitialized { initialized: true },
+    ];
+    for action in actions.iter() {
+        action.apply(&mut migration, Some(&mut dao_liq)).unwrap();
+    }
+
+    assert_eq!(migration.snapshot_root, [9u8; 32]);
+    assert_eq!(migration.manifest_hash, [8u8; 32]);
+    assert!(!migration.paused);
+    assert_eq!(dao_liq.dao_treasury, new_treasury);
+    // A handover in flight is dropped along with the old treasury
+    assert_eq!(dao_liq.pending_treasury, Pubkey::default());
+    assert!(dao_liq.pool_initialized);
+
+    println!("✓ Proposal actions applied");
+}
+
+#[test]
+fn test_treasury_actions_need_dao_liquidity() {
+    let mut migration = migration(Pubkey::new_unique());
+
+    for action in [
+        ProposalAction::SetDaoTreasury { treasury: Pubkey::new_unique() },
+        ProposalAction::SetPoolInitialized { initialized: true },
+    ] {
+        assert_eq!(action.apply(&mut migration, None).unwrap_err(), ErrorCode::InvalidOperation.into());
+    }
+}
+
+#[test]
+fn test_veto_action_is_not_executable() {
+    // Vetoes go through veto_operation_by_vote, never execute_proposal
+    let mut migration = migration(Pubkey::new_unique());
+    let veto = ProposalAction::VetoOperation { operation: Pubkey::new_unique() };
+    assert_eq!(veto.apply(&mut migration, None).unwrap_err(), ErrorCode::InvalidOperation.into());
+}
+
+#[test]
+fn test_veto_must_be_sole_action() {
+    let veto = ProposalAction::VetoOperation { operation: Pubkey::new_unique() };
+    validate_actions(&[veto]).unwrap();
+    validate_actions(&[ProposalAction::Unpause, ProposalAction::SetPoolInitialized { initialized: true }]).unwrap();
+
+    // veto_operation_by_vote would mark the proposal executed and Unpause would never run
+    let err = validate_actions(&[veto, ProposalAction::Unpause]).unwrap_err();
+    assert_eq!(err, ErrorCode::VetoNotSoleAction.into());
+
+    let err = validate_actions(&[ProposalAction::Unpause; MAX_PROPOSAL_ACTIONS + 1]).unwrap_err();
+    assert_eq!(err, ErrorCode::TooManyActions.into());
+    println!("✓ Vetoes can't be mixed with other actions");
+}
+
+#[test]
+fn test_max_actions_fit_proposal_space() {
+    let mut full = proposal(0, 0);
+    let largest = ProposalAction::QueueOperation {
+        kind: OperationKind::AddSnapshotEpoch {
+            snapshot_root: [0xff; 32],
+            manifest_hash: [0xff; 32],
+            supply_cap: u64::MAX,
+            claim_start: i64::MAX,
+            claim_end: i64::MAX,
+        },
+    };
+    full.actions = vec![largest; MAX_PROPOSAL_ACTIONS];
+
+    let space = std::mem::size_of::<Proposal>()
+        + MAX_PROPOSAL_ACTIONS * std::mem::size_of::<ProposalAction>();
+    assert!(full.try_to_vec().unwrap().len() <= space);
+}
+
+#[test]
+fn test_timelocked_actions_wait_as_long_as_the_admin_would() {
+    let rotate = ProposalAction::SetSnapshotRoot { root: [9u8; 32], manifest_hash: [8u8; 32] };
+    assert!(rotate.is_timelocked());
+    assert!(ProposalAction::SetDaoTreasury { treasury: Pubkey::new_unique() }.is_timelocked());
+    assert!(!ProposalAction::Unpause.is_timelocked());
+
+    let mut runtime = Runtime::new();
+    let state = Migration { timelock_delay: 86_400, ..migration(Pubkey::default()) };
+    let (migration, governance) = governed_migration(&mut runtime, state, 3_600);
+
+    // A one-hour proposal delay must not undercut the day-long timelock
+    let proposal = passed_proposal(&mut runtime, governance, vec![rotate, ProposalAction::Unpause]);
+    let err = runtime.process(&execute_proposal_ix(execute_accounts(migration, governance, proposal))).unwrap_err();
+    assert_eq!(err, runtime::program_error(ErrorCode::TimelockRequired));
+    assert_eq!(runtime.account::<Proposal>(&proposal).status, ProposalStatus::Passed);
+
+    let proposal = passed_proposal(&mut runtime, governance, vec![ProposalAction::Unpause]);
+    runtime.process(&execute_proposal_ix(execute_accounts(migration, governance, proposal))).unwrap();
+    assert!(!runtime.account::<Migration>(&migration).paused);
+
+    // Once the proposal delay covers the timelock, the rotation runs directly
+    let state = Migration { timelock_delay: 3_600, ..self::migration(Pubkey::default()) };
+    let (migration, governance) = governed_migration(&mut runtime, state, 3_600);
+    let proposal = passed_proposal(&mut runtime, governance, vec![rotate]);
+    runtime.process(&execute_proposal_ix(execute_accounts(migration, governance, proposal))).unwrap();
+    assert_eq!(runtime.account::<Migration>(&migration).snapshot_root, [9u8; 32]);
+
+    println!("✓ Governance can't skip the migration timelock");
+}
+
+fn epoch_action(now: i64) -> ProposalAction {
+    ProposalAction::AddSnapshotEpoch {
+        snapshot_root: [5u8; 32],
+        manifest_hash: [6u8; 32],
+        supply_cap: 100,
+        claim_start: now,
+        claim_end: now + 1_000,
+    }
+}
+
+#[test]
+fn test_governance_owned_migration_can_be_finalized() {
+    let mut runtime = Runtime::new();
+    let state = Migration { paused: false, ..migration(Pubkey::new_unique()) };
+    let admin = runtime.add(TestAccount::signer(state.authority));
+    let mint = state.mint;
+    let migration = runtime.add_migration(state);
+    let (governance, _) = Pubkey::find_program_address(&[b"governance", migration.as_ref()], &necro_migrate::ID);
+
+    let initialize_governance = Instruction {
+        program_id: necro_migrate::ID,
+        accounts: necro_migrate::accounts::InitializeGovernance {
+            admin,
+            migration,
+            governance,
+            mint,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: necro_migrate::instruction::InitializeGovernance {
+            voting_period: 100,
+            quorum_voters: 1,
+            quorum_weight: 1,
+            execution_delay: 0,
+        }
+        .data(),
+    };
+    runtime.process(&initialize_governance).unwrap();
+
+    // One governance per migration: the PDA already exists, so `init` fails
+    let err = runtime.process(&initialize_governance).unwrap_err();
+    assert_eq!(err, ProgramError::Custom(SystemError::AccountAlreadyInUse as u32));
+
+    runtime
+        .process(&Instruction {
+            program_id: necro_migrate::ID,
+            accounts: necro_migrate::accounts::UpdateMigration { admin, migration }.to_account_metas(None),
+            data: necro_migrate::instruction::ProposeAdmin { new_authority: governance }.data(),
+        })
+        .unwrap();
+    runtime
+        .process(&Instruction {
+            program_id: necro_migrate::ID,
+            accounts: necro_migrate::accounts::AcceptGovernanceAdmin { migration, governance }.to_account_metas(None),
+            data: necro_migrate::instruction::AcceptGovernanceAdmin {}.data(),
+        })
+        .unwrap();
+
+    // The old admin key no longer works, and the governance PDA can't sign
+    let err = runtime
+        .process(&Instruction {
+            program_id: necro_migrate::ID,
+            accounts: necro_migrate::accounts::FinalizeMigration { admin, migration }.to_account_metas(None),
+            data: necro_migrate::instruction::FinalizeMigration {}.data(),
+        })
+        .unwrap_err();
+    assert_eq!(err, runtime::program_error(ErrorCode::Unauthorized));
+
+    let payer = runtime.add(TestAccount::signer(Pubkey::new_unique()));
+    let (snapshot_epoch, _) =
+        Pubkey::find_program_address(&[b"epoch", migration.as_ref(), &1u32.to_le_bytes()], &necro_migrate::ID);
+    let actions = vec![epoch_action(runtime.now()), ProposalAction::Finalize];
+    let proposal = passed_proposal(&mut runtime, governance, actions);
+    runtime
+        .process(&execute_proposal_ix(necro_migrate::accounts::ExecuteProposal {
+            payer: Some(payer),
+            snapshot_epoch: Some(snapshot_epoch),
+            system_program: Some(system_program::ID),
+            ..execute_accounts(migration, governance, proposal)
+        }))
+        .unwrap();
+
+    let state: Migration = runtime.account(&migration);
+    assert!(!state.is_active);
+    assert_eq!(state.epoch_count, 1);
+    assert_eq!(runtime.account::<necro_migrate::SnapshotEpoch>(&snapshot_epoch).snapshot_root, [5u8; 32]);
+    assert_eq!(runtime.account::<Proposal>(&proposal).status, ProposalStatus::Executed);
+
+    println!("✓ Governance finalizes the migration it administers");
+}
+
+#[test]
+fn test_governance_queues_operations_under_timelock() {
+    let mut runtime = Runtime::new();
+    let state = Migration { timelock_delay: 86_400, ..migration(Pubkey::default()) };
+    let (migration, governance) = governed_migration(&mut runtime, state, 0);
+    let payer = runtime.add(TestAccount::signer(Pubkey::new_unique()));
+    let now = runtime.now();
+
+    // Adding the epoch directly would skip the timelock
+    let proposal = passed_proposal(&mut runtime, governance, vec![epoch_action(now)]);
+    let err = runtime.process(&execute_proposal_ix(execute_accounts(migration, governance, proposal))).unwrap_err();
+    assert_eq!(err, runtime::program_error(ErrorCode::TimelockRequired));
+
+    let ProposalAction::AddSnapshotEpoch { snapshot_root, manifest_hash, supply_cap, claim_start, claim_end } =
+        epoch_action(now)
+    else {
+        unreachable!()
+    };
+    let kind = OperationKind::AddSnapshotEpoch { snapshot_root, manifest_hash, supply_cap, claim_start, claim_end };
+    let (queued_operation, _) = Pubkey::find_program_address(
+        &[b"queued_op", migration.as_ref(), &0u64.to_le_bytes()],
+        &necro_migrate::ID,
+    );
+    let proposal = passed_proposal(&mut runtime, governance, vec![ProposalAction::QueueOperation { kind }]);
+    runtime
+        .process(&execute_proposal_ix(necro_migrate::accounts::ExecuteProposal {
+            payer: Some(payer),
+            queued_operation: Some(queued_operation),
+            system_program: Some(system_program::ID),
+            ..execute_accounts(migration, governance, proposal)
+        }))
+        .unwrap();
+
+    let operation: QueuedOperation = runtime.account(&queued_operation);
+    assert_eq!((operation.id, operation.kind, operation.eta), (0, kind, now + 86_400));
+    assert_eq!(operation.status, OperationStatus::Queued);
+    assert_eq!(runtime.account::<Migration>(&migration).operation_count, 1);
+
+    // From here it is an ordinary queued operation the guardian can still veto
+    runtime.warp(86_400);
+    let (snapshot_epoch, _) =
+        Pubkey::find_program_address(&[b"epoch", migration.as_ref(), &1u32.to_le_bytes()], &necro_migrate::ID);
+    runtime
+        .process(&Instruction {
+            program_id: necro_migrate::ID,
+            accounts: necro_migrate::accounts::ExecuteOperation {
+                migration,
+                queued_operation,
+                dao_liquidity: None,
+                payer: Some(payer),
+                snapshot_epoch: Some(snapshot_epoch),
+                system_program: Some(system_program::ID),
+            }
+            .to_account_metas(None),
+            data: necro_migrate::instruction::ExecuteOperation {}.data(),
+        })
+        .unwrap();
+    assert_eq!(runtime.account::<Migration>(&migration).epoch_count, 1);
+
+    println!("✓ Governance queues timelocked operations");
+}
+
+#[test]
+fn test_one_account_creating_action_of_each_kind() {
+    let queue = ProposalAction::QueueOperation { kind: OperationKind::Finalize };
+    validate_actions(&[queue, epoch_action(0), ProposalAction::Finalize]).unwrap();
+
+    let err = validate_actions(&[queue, queue]).unwrap_err();
+    assert_eq!(err, ErrorCode::DuplicateAccountAction.into());
+    let err = validate_actions(&[epoch_action(0), epoch_action(0)]).unwrap_err();
+    assert_eq!(err, ErrorCode::DuplicateAccountAction.into());
+
+    // Queued kinds are checked when the proposal is created
+    let err = validate_actions(&[ProposalAction::QueueOperation { kind: OperationKind::SetTimelockDelay { delay: -1 } }])
+        .unwrap_err();
+    assert_eq!(err, ErrorCode::InvalidOperation.into());
+}
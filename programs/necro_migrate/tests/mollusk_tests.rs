@@ -2,6 +2,8 @@
 
 use solana_sdk::pubkey::Pubkey;
 
+mod common;
+
 // Program ID (matches the deployed program)
 const NECRO_MIGRATE_PROGRAM_ID: &str = "2z3U1Wwq7bgHnkEuD5Yfw97g8uGyimDyRafRar21Bsva";
 
@@ -9,9 +11,9 @@ const NECRO_MIGRATE_PROGRAM_ID: &str = "2z3U1Wwq7bgHnkEuD5Yfw97g8uGyimDyRafRar21
 #[test]
 fn test_initialize_migration() {
     // Create keypairs for test accounts
-    let admin = Pubkey::new_unique();
-    let migration_pda = Pubkey::new_unique();
-    let mint = Pubkey::new_unique();
+    let _admin = Pubkey::new_unique();
+    let _migration_pda = Pubkey::new_unique();
+    let _mint = Pubkey::new_unique();
     
     // Prepare migration parameters
     let migration_name = [0u8; 64];
@@ -91,16 +93,245 @@ fn test_account_sizes() {
     // - user: Pubkey = 32 bytes
     // - is_claimed: bool = 1 byte
     // - amount: u64 = 8 bytes
-    let user_claim_size = 32 + 1 + 8;
+    // - net_amount: u64 = 8 bytes
+    let user_claim_size = 32 + 1 + 8 + 8;
     
     assert_eq!(migration_size, 180);
-    assert_eq!(user_claim_size, 41);
+    assert_eq!(user_claim_size, 49);
     
     println!("✓ Account size calculations verified");
     println!("  - Migration: {} bytes (8 + {} for discriminator + data)", 8 + migration_size, migration_size);
     println!("  - UserClaim: {} bytes (8 + {} for discriminator + data)", 8 + user_claim_size, user_claim_size);
 }
 
+fn sample_migration(total_supply: u64) -> necro_migrate::Migration {
+    necro_migrate::Migration { total_supply, ..common::migration() }
+}
+
+#[test]
+fn test_migrated_amount_tracking() {
+    let mut migration = sample_migration(1_000);
+
+    migration.record_claim(400).unwrap();
+    migration.record_claim(600).unwrap();
+    assert_eq!(migration.migrated_amount, 1_000);
+    assert_eq!(migration.remaining_supply(), 0);
+
+    println!("✓ migrated_amount tracks claims up to total_supply");
+}
+
+#[test]
+fn test_supply_cap_enforced() {
+    let mut migration = sample_migration(1_000);
+    migration.record_claim(999).unwrap();
+
+    let err = migration.record_claim(2).unwrap_err();
+    assert_eq!(err, necro_migrate::ErrorCode::SupplyCapExceeded.into());
+    // Failed claims leave the counter untouched
+    assert_eq!(migration.migrated_amount, 999);
+    assert_eq!(migration.remaining_supply(), 1);
+
+    // Overflow is reported as the same cap error rather than wrapping
+    migration.total_supply = u64::MAX;
+    let err = migration.record_claim(u64::MAX).unwrap_err();
+    assert_eq!(err, necro_migrate::ErrorCode::SupplyCapExceeded.into());
+
+    println!("✓ Supply cap enforced with checked arithmetic");
+}
+
+#[test]
+fn test_mint_on_claim_capped_by_total_supply() {
+    // Nothing is pre-minted in this mode, so the claim counter is the only cap on minting
+    let mut migration = sample_migration(1_000);
+    migration.supply_mode = necro_migrate::SupplyMode::MintOnClaim;
+
+    migration.record_claim(1_000).unwrap();
+    let err = migration.record_claim(1).unwrap_err();
+    assert_eq!(err, necro_migrate::ErrorCode::SupplyCapExceeded.into());
+
+    println!("✓ Mint-on-claim capped at total_supply");
+}
+
+#[test]
+fn test_claim_deadline() {
+    let mut migration = sample_migration(1_000);
+    assert!(migration.check_claimable(i64::MAX).is_ok());
+
+    migration.claim_deadline = 1_700_000_000;
+    assert!(migration.check_claimable(1_699_999_999).is_ok());
+    let err = migration.check_claimable(1_700_000_000).unwrap_err();
+    assert_eq!(err, necro_migrate::ErrorCode::ClaimDeadlinePassed.into());
+
+    migration.is_active = false;
+    let err = migration.check_claimable(0).unwrap_err();
+    assert_eq!(err, necro_migrate::ErrorCode::MigrationNotActive.into());
+
+    println!("✓ Claims stop at the deadline");
+}
+
+/// `set_claim_deadline` signed by the migration authority
+fn set_claim_deadline_ix(
+    migration: Pubkey,
+    admin: Pubkey,
+    claim_deadline: i64,
+) -> anchor_lang::solana_program::instruction::Instruction {
+    use anchor_lang::{InstructionData, ToAccountMetas};
+
+    anchor_lang::solana_program::instruction::Instruction {
+        program_id: necro_migrate::ID,
+        accounts: necro_migrate::accounts::UpdateMigration { admin, migration }.to_account_metas(None),
+        data: necro_migrate::instruction::SetClaimDeadline { claim_deadline }.data(),
+    }
+}
+
+#[test]
+fn test_claim_deadline_can_only_be_extended() {
+    use anchor_lang::{InstructionData, ToAccountMetas};
+    use common::runtime::{self, Runtime};
+
+    let mut runtime = Runtime::new();
+    let state = sample_migration(1_000);
+    let admin = runtime.add(common::TestAccount::signer(state.authority));
+    let migration = runtime.add_migration(state);
+    let now = runtime.now();
+
+    runtime.process(&set_claim_deadline_ix(migration, admin, now + 100)).unwrap();
+    runtime.process(&set_claim_deadline_ix(migration, admin, now + 200)).unwrap();
+
+    // Bringing the deadline forward would cut holders' claim window short
+    let err = runtime.process(&set_claim_deadline_ix(migration, admin, now + 150)).unwrap_err();
+    assert_eq!(err, runtime::program_error(necro_migrate::ErrorCode::InvalidOperation));
+
+    // Once it has passed and the leftovers are swept, the deadline is final
+    runtime.warp(200);
+    let state: necro_migrate::Migration = runtime.account(&migration);
+    let (dao_liquidity, _) =
+        Pubkey::find_program_address(&[b"dao_liquidity", migration.as_ref()], &necro_migrate::ID);
+    runtime
+        .process(&anchor_lang::solana_program::instruction::Instruction {
+            program_id: necro_migrate::ID,
+            accounts: necro_migrate::accounts::SweepUnclaimed {
+                migration,
+                dao_liquidity,
+                mint: state.mint,
+                token_vault: state.token_vault,
+                treasury_token_account: None,
+                migration_authority: runtime::authority_pda(&migration).0,
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None),
+            data: necro_migrate::instruction::SweepUnclaimed {}.data(),
+        })
+        .unwrap();
+    assert_eq!(runtime.token_amount(&state.token_vault), 0);
+
+    let err = runtime.process(&set_claim_deadline_ix(migration, admin, now + 10_000)).unwrap_err();
+    assert_eq!(err, runtime::program_error(necro_migrate::ErrorCode::ClaimDeadlinePassed));
+    assert_eq!(runtime.account::<necro_migrate::Migration>(&migration).claim_deadline, now + 200);
+
+    println!("✓ Claim deadline only moves later and is final once passed");
+}
+
+#[test]
+fn test_paused_migration_rejects_claims() {
+    let mut migration = sample_migration(1_000);
+    migration.paused = true;
+
+    let err = migration.check_claimable(0).unwrap_err();
+    assert_eq!(err, necro_migrate::ErrorCode::MigrationPaused.into());
+
+    migration.paused = false;
+    assert!(migration.check_claimable(0).is_ok());
+
+    println!("✓ Pause flag blocks claims");
+}
+
+#[test]
+fn test_two_step_admin_transfer() {
+    let mut migration = sample_migration(1_000);
+    let seed_admin = migration.admin;
+    let current = migration.authority;
+    let dao_pda = Pubkey::new_unique();
+    let stranger = Pubkey::new_unique();
+
+    // Only the current authority can propose, and nothing changes until accepted
+    let err = migration.propose_authority(&stranger, stranger).unwrap_err();
+    assert_eq!(err, necro_migrate::ErrorCode::Unauthorized.into());
+    migration.propose_authority(&current, dao_pda).unwrap();
+    assert_eq!(migration.authority, current);
+
+    let err = migration.accept_authority(&stranger).unwrap_err();
+    assert_eq!(err, necro_migrate::ErrorCode::Unauthorized.into());
+    migration.accept_authority(&dao_pda).unwrap();
+
+    assert_eq!(migration.authority, dao_pda);
+    assert_eq!(migration.pending_authority, Pubkey::default());
+    // PDA seeds still use the original creator
+    assert_eq!(migration.admin, seed_admin);
+
+    // The old authority has lost control and can't re-accept
+    let err = migration.propose_authority(&current, current).unwrap_err();
+    assert_eq!(err, necro_migrate::ErrorCode::Unauthorized.into());
+    let err = migration.accept_authority(&Pubkey::default()).unwrap_err();
+    assert_eq!(err, necro_migrate::ErrorCode::Unauthorized.into());
+
+    println!("✓ Admin handed over in two steps");
+}
+
+#[test]
+fn test_epoch_zero_keeps_original_claim_pda() {
+    use necro_migrate::UserClaim;
+
+    let program_id: Pubkey = NECRO_MIGRATE_PROGRAM_ID.parse().unwrap();
+    let migration_pda = Pubkey::new_unique();
+    let user = Pubkey::new_unique();
+
+    let (original, _) = Pubkey::find_program_address(
+        &[b"claim", migration_pda.as_ref(), user.as_ref()],
+        &program_id,
+    );
+    let (epoch_zero, _) = Pubkey::find_program_address(
+        &[b"claim", migration_pda.as_ref(), user.as_ref(), UserClaim::epoch_seed(&0u32.to_le_bytes())],
+        &program_id,
+    );
+    let (epoch_one, _) = Pubkey::find_program_address(
+        &[b"claim", migration_pda.as_ref(), user.as_ref(), UserClaim::epoch_seed(&1u32.to_le_bytes())],
+        &program_id,
+    );
+
+    assert_eq!(original, epoch_zero);
+    assert_ne!(original, epoch_one);
+    println!("✓ Claims are tracked per epoch without moving epoch 0 PDAs");
+}
+
+#[test]
+fn test_snapshot_epoch_window_and_cap() {
+    let mut epoch = necro_migrate::SnapshotEpoch {
+        migration: Pubkey::new_unique(),
+        epoch: 1,
+        snapshot_root: [2u8; 32],
+        supply_cap: 500,
+        claimed_amount: 0,
+        claim_start: 1_000,
+        claim_end: 2_000,
+        bump: 255,
+        manifest_hash: [3u8; 32],
+    };
+
+    assert!(!epoch.is_open(999));
+    assert!(epoch.is_open(1_000));
+    assert!(epoch.is_open(1_999));
+    assert!(!epoch.is_open(2_000));
+
+    epoch.record_claim(300).unwrap();
+    epoch.record_claim(200).unwrap();
+    let err = epoch.record_claim(1).unwrap_err();
+    assert_eq!(err, necro_migrate::ErrorCode::EpochCapExceeded.into());
+    assert_eq!(epoch.claimed_amount, 500);
+
+    println!("✓ Snapshot epoch enforces its window and cap");
+}
+
 #[test]
 fn test_merkle_proof_structure() {
     // Test merkle proof validation structure
@@ -131,6 +362,119 @@ fn test_merkle_proof_structure() {
     println!("✓ Merkle proof structure verified");
 }
 
+#[test]
+fn test_merkle_proof_verification() {
+    use necro_migrate::merkle;
+    use solana_program::hash::hashv;
+
+    let wallets: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+    let leaves: Vec<[u8; 32]> = wallets
+        .iter()
+        .enumerate()
+        .map(|(i, wallet)| merkle::leaf_hash(wallet, 1_000 * (i as u64 + 1), i as u32))
+        .collect();
+
+    let node = |a: [u8; 32], b: [u8; 32]| {
+        if a < b { hashv(&[&a, &b]).to_bytes() } else { hashv(&[&b, &a]).to_bytes() }
+    };
+    let left = node(leaves[0], leaves[1]);
+    let right = node(leaves[2], leaves[3]);
+    let root = node(left, right);
+
+    assert!(merkle::verify(leaves[2], &[leaves[3], left], &root));
+    assert!(!merkle::verify(leaves[2], &[leaves[1], left], &root));
+
+    // Wrong amount or index produces a different leaf
+    let forged = merkle::leaf_hash(&wallets[2], 9_999, 2);
+    assert!(!merkle::verify(forged, &[leaves[3], left], &root));
+
+    println!("✓ Merkle proof verification");
+}
+
+#[test]
+fn test_merkle_v1_domain_separation() {
+    use necro_migrate::merkle::{self, HashVersion, SnapshotHasher, LEAF_PREFIX, NODE_PREFIX};
+    use solana_program::hash::hashv;
+
+    let migration = Pubkey::new_unique();
+    let hasher = SnapshotHasher::new(HashVersion::V1, migration);
+    let wallets: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+    let leaves: Vec<[u8; 32]> = wallets
+        .iter()
+        .enumerate()
+        .map(|(i, wallet)| hasher.leaf_hash(wallet, 1_000 * (i as u64 + 1), i as u32))
+        .collect();
+
+    let expected_leaf = hashv(&[
+        &[LEAF_PREFIX],
+        necro_migrate::ID.as_ref(),
+        migration.as_ref(),
+        wallets[0].as_ref(),
+        &1_000u64.to_le_bytes(),
+        &0u32.to_le_bytes(),
+    ])
+    .to_bytes();
+    assert_eq!(leaves[0], expected_leaf);
+    assert_ne!(leaves[0], merkle::leaf_hash(&wallets[0], 1_000, 0));
+
+    let (lo, hi) = if leaves[0] < leaves[1] { (leaves[0], leaves[1]) } else { (leaves[1], leaves[0]) };
+    let left = hasher.node_hash(&leaves[0], &leaves[1]);
+    assert_eq!(left, hashv(&[&[NODE_PREFIX], &lo, &hi]).to_bytes());
+    assert_eq!(left, hasher.node_hash(&leaves[1], &leaves[0]));
+
+    let right = hasher.node_hash(&leaves[2], &leaves[3]);
+    let root = hasher.node_hash(&left, &right);
+    assert!(hasher.verify(leaves[2], &[leaves[3], left], &root));
+
+    // The same root and proof don't verify for another migration
+    let other = SnapshotHasher::new(HashVersion::V1, Pubkey::new_unique());
+    let replayed = other.leaf_hash(&wallets[2], 3_000, 2);
+    assert!(!other.verify(replayed, &[leaves[3], left], &root));
+
+    // Legacy hashing still verifies roots built with the free functions
+    let legacy = SnapshotHasher::new(HashVersion::Legacy, migration);
+    let legacy_leaves: Vec<[u8; 32]> = wallets
+        .iter()
+        .enumerate()
+        .map(|(i, wallet)| merkle::leaf_hash(wallet, 1_000 * (i as u64 + 1), i as u32))
+        .collect();
+    let legacy_left = legacy.node_hash(&legacy_leaves[0], &legacy_leaves[1]);
+    let legacy_root = legacy.node_hash(&legacy_left, &legacy.node_hash(&legacy_leaves[2], &legacy_leaves[3]));
+    assert_eq!(legacy.leaf_hash(&wallets[2], 3_000, 2), legacy_leaves[2]);
+    assert!(merkle::verify(legacy_leaves[0], &[legacy_leaves[1], legacy.node_hash(&legacy_leaves[2], &legacy_leaves[3])], &legacy_root));
+    assert!(legacy.verify(legacy_leaves[3], &[legacy_leaves[2], legacy_left], &legacy_root));
+
+    println!("✓ V1 leaves and nodes are prefixed and bound to the migration");
+}
+
+#[test]
+fn test_merkle_proofs_from_shared_tree_builder() {
+    use necro_migrate::merkle::{HashVersion, SnapshotHasher};
+    use necro_merkle::{MerkleTree, Sha256};
+
+    let migration = Pubkey::new_unique();
+    let builder = necro_merkle::SnapshotHasher::<Sha256>::new(
+        necro_merkle::HashVersion::V1,
+        necro_migrate::ID.to_bytes(),
+        migration.to_bytes(),
+    );
+    let hasher = SnapshotHasher::new(HashVersion::V1, migration);
+
+    let wallets: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+    let leaves: Vec<[u8; 32]> = wallets
+        .iter()
+        .enumerate()
+        .map(|(i, wallet)| builder.wallet_leaf(&wallet.to_bytes(), 100 * i as u64, i as u32))
+        .collect();
+    let tree = MerkleTree::new(&builder, leaves).unwrap();
+
+    for (i, wallet) in wallets.iter().enumerate() {
+        let leaf = hasher.leaf_hash(wallet, 100 * i as u64, i as u32);
+        assert!(hasher.verify(leaf, &tree.proof(i).unwrap(), &tree.root()));
+    }
+    println!("✓ Off-chain trees verify with the on-chain hasher");
+}
+
 #[test]
 fn test_governance_structure() {
     // Test governance account structure
@@ -138,9 +482,16 @@ fn test_governance_structure() {
     // Governance account should contain:
     // - migration: Pubkey = 32 bytes
     // - total_votes: u64 = 8 bytes
-    let governance_size = 32 + 8;
+    // - mint: Pubkey = 32 bytes
+    // - proposal_count: u64 = 8 bytes
+    // - voting_period: i64 = 8 bytes
+    // - bump: u8 = 1 byte
+    // - quorum_voters: u32 = 4 bytes
+    // - quorum_weight: u64 = 8 bytes
+    // - execution_delay: i64 = 8 bytes
+    let governance_size = 32 + 8 + 32 + 8 + 8 + 1 + 4 + 8 + 8;
     
-    assert_eq!(governance_size, 40);
+    assert_eq!(governance_size, 109);
     
     println!("✓ Governance account structure verified");
     println!("  - Governance: {} bytes (8 + {} for discriminator + data)", 8 + governance_size, governance_size);
@@ -153,7 +504,6 @@ fn test_error_codes() {
     // Expected error codes:
     const INVALID_OPERATION: u32 = 0;
     const MIGRATION_NOT_ACTIVE: u32 = 6000;
-    const INVALID_AMOUNT: u32 = 6001;
     const ALREADY_CLAIMED: u32 = 6002;
     const INVALID_MERKLE_PROOF: u32 = 6003;
     const UNAUTHORIZED: u32 = 6004;
@@ -176,7 +526,7 @@ fn test_program_deployment_verification() {
     let program_id: Pubkey = NECRO_MIGRATE_PROGRAM_ID.parse().unwrap();
     
     // Check program ID is valid
-    assert!(program_id.to_string().len() > 0);
+    assert!(!program_id.to_string().is_empty());
     
     // Verify program ID matches the one we deployed
     assert_eq!(
@@ -0,0 +1,105 @@
+#![cfg(test)]
+
+use necro_migrate::ed25519::{relayed_claim_message, verify_instruction};
+use necro_migrate::ErrorCode;
+use solana_sdk::ed25519_program;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+/// Build an Ed25519 precompile instruction the way the client SDKs do:
+/// header, offsets, then pubkey | signature | message in the same instruction
+fn ed25519_ix(signer: &Keypair, message: &[u8]) -> Instruction {
+    let signature = signer.sign_message(message);
+    let public_key_offset: u16 = 16;
+    let signature_offset = public_key_offset + 32;
+    let message_offset = signature_offset + 64;
+
+    let mut data = vec![1u8, 0];
+    for value in [
+        signature_offset,
+        u16::MAX,
+        public_key_offset,
+        u16::MAX,
+        message_offset,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(signer.pubkey().as_ref());
+    data.extend_from_slice(signature.as_ref());
+    data.extend_from_slice(message);
+
+    Instruction { program_id: ed25519_program::id(), accounts: vec![], data }
+}
+
+#[test]
+fn test_relayed_claim_message_layout() {
+    let migration = Pubkey::new_unique();
+    let recipient_ata = Pubkey::new_unique();
+    let message = relayed_claim_message(&migration, 1_000, 7, &recipient_ata);
+
+    assert_eq!(message.len(), 32 + 8 + 4 + 32);
+    assert_eq!(&message[..32], migration.as_ref());
+    assert_eq!(&message[32..40], &1_000u64.to_le_bytes());
+    assert_eq!(&message[40..44], &7u32.to_le_bytes());
+    assert_eq!(&message[44..], recipient_ata.as_ref());
+}
+
+#[test]
+fn test_accepts_beneficiary_signature() {
+    let beneficiary = Keypair::new();
+    let message = relayed_claim_message(&Pubkey::new_unique(), 500, 3, &Pubkey::new_unique());
+    let ix = ed25519_ix(&beneficiary, &message);
+
+    verify_instruction(&ix, &beneficiary.pubkey(), &message).unwrap();
+    println!("✓ Relayed claim authorized by beneficiary signature");
+}
+
+#[test]
+fn test_rejects_other_signer() {
+    let beneficiary = Keypair::new();
+    let relayer = Keypair::new();
+    let message = relayed_claim_message(&Pubkey::new_unique(), 500, 3, &Pubkey::new_unique());
+    let ix = ed25519_ix(&relayer, &message);
+
+    let err = verify_instruction(&ix, &beneficiary.pubkey(), &message).unwrap_err();
+    assert_eq!(err, ErrorCode::InvalidSignature.into());
+}
+
+#[test]
+fn test_rejects_redirected_recipient() {
+    // Relayer swaps in its own token account after the beneficiary signed
+    let beneficiary = Keypair::new();
+    let migration = Pubkey::new_unique();
+    let signed = relayed_claim_message(&migration, 500, 3, &Pubkey::new_unique());
+    let ix = ed25519_ix(&beneficiary, &signed);
+
+    let submitted = relayed_claim_message(&migration, 500, 3, &Pubkey::new_unique());
+    let err = verify_instruction(&ix, &beneficiary.pubkey(), &submitted).unwrap_err();
+    assert_eq!(err, ErrorCode::InvalidSignature.into());
+}
+
+#[test]
+fn test_rejects_non_precompile_instruction() {
+    let beneficiary = Keypair::new();
+    let message = relayed_claim_message(&Pubkey::new_unique(), 500, 3, &Pubkey::new_unique());
+    let mut ix = ed25519_ix(&beneficiary, &message);
+    ix.program_id = Pubkey::new_unique();
+
+    let err = verify_instruction(&ix, &beneficiary.pubkey(), &message).unwrap_err();
+    assert_eq!(err, ErrorCode::InvalidSignature.into());
+}
+
+#[test]
+fn test_rejects_data_from_other_instruction() {
+    let beneficiary = Keypair::new();
+    let message = relayed_claim_message(&Pubkey::new_unique(), 500, 3, &Pubkey::new_unique());
+    let mut ix = ed25519_ix(&beneficiary, &message);
+    // message_instruction_index -> instruction 0 instead of this one
+    ix.data[14..16].copy_from_slice(&0u16.to_le_bytes());
+
+    let err = verify_instruction(&ix, &beneficiary.pubkey(), &message).unwrap_err();
+    assert_eq!(err, ErrorCode::InvalidSignature.into());
+}
@@ -0,0 +1,290 @@
+#![cfg(test)]
+
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{system_program, AnchorSerialize, InstructionData, ToAccountMetas};
+use common::runtime::{self, Runtime};
+use common::TestAccount;
+use necro_migrate::timelock::{OperationKind, OperationStatus, MAX_TIMELOCK_DELAY};
+use necro_migrate::{DAOLiquidity, ErrorCode, Migration, QueuedOperation, SnapshotEpoch};
+use solana_sdk::pubkey::Pubkey;
+
+mod common;
+
+fn migration() -> Migration {
+    Migration { timelock_delay: 86_400, ..common::migration() }
+}
+
+fn queued(kind: OperationKind, eta: i64) -> QueuedOperation {
+    QueuedOperation {
+        migration: Pubkey::new_unique(),
+        id: 0,
+        kind,
+        eta,
+        status: OperationStatus::Queued,
+        bump: 255,
+    }
+}
+
+#[test]
+fn test_operation_waits_for_eta() {
+    let mut operation = queued(OperationKind::Finalize, 1_000);
+
+    assert_eq!(operation.check_executable(999).unwrap_err(), ErrorCode::TimelockNotExpired.into());
+    operation.check_executable(1_000).unwrap();
+
+    operation.status = OperationStatus::Executed;
+    assert_eq!(operation.check_executable(2_000).unwrap_err(), ErrorCode::OperationNotQueued.into());
+
+    println!("✓ Queued operation respects ETA");
+}
+
+#[test]
+fn test_cancelled_or_vetoed_operation_never_executes() {
+    for status in [OperationStatus::Cancelled, OperationStatus::Vetoed] {
+        let mut operation = queued(OperationKind::Finalize, 0);
+        operation.status = status;
+        assert_eq!(operation.check_pending().unwrap_err(), ErrorCode::OperationNotQueued.into());
+        assert_eq!(operation.check_executable(i64::MAX).unwrap_err(), ErrorCode::OperationNotQueued.into());
+    }
+}
+
+#[test]
+fn test_operations_apply_to_migration() {
+    let mut migration = migration();
+    let mut dao_liq = DAOLiquidity {
+        migration: Pubkey::new_unique(),
+        dao_treasury: Pubkey::new_unique(),
+        lp_percentage: 10,
+        pool_initialized: false,
+        total_reserved: 0,
+        bump: 255,
+        pending_treasury: Pubkey::new_unique(),
+        amm_program: Pubkey::default(),
+        lp_mint: Pubkey::default(),
+    };
+    let treasury = Pubkey::new_unique();
+
+    let guardian = Pubkey::new_unique();
+    let now = 1_000;
+
+    OperationKind::SetSnapshotRoot { root: [7u8; 32], manifest_hash: [6u8; 32] }.apply(&mut migration, None, now).unwrap();
+    OperationKind::SetDaoTreasury { treasury }.apply(&mut migration, Some(&mut dao_liq), now).unwrap();
+    OperationKind::SetTimelockDelay { delay: 3_600 }.apply(&mut migration, None, now).unwrap();
+    OperationKind::SetClaimDeadline { claim_deadline: 5_000 }.apply(&mut migration, None, now).unwrap();
+    OperationKind::SetGuardian { guardian }.apply(&mut migration, None, now).unwrap();
+    OperationKind::Finalize.apply(&mut migration, None, now).unwrap();
+
+    assert_eq!(migration.snapshot_root, [7u8; 32]);
+    assert_eq!(migration.manifest_hash, [6u8; 32]);
+    assert_eq!(dao_liq.dao_treasury, treasury);
+    assert_eq!(dao_liq.pending_treasury, Pubkey::default());
+    assert_eq!(migration.timelock_delay, 3_600);
+    assert_eq!(migration.claim_deadline, 5_000);
+    assert_eq!(migration.guardian, guardian);
+    assert!(!migration.is_active);
+
+    assert_eq!(
+        OperationKind::SetDaoTreasury { treasury }.apply(&mut migration, None, now).unwrap_err(),
+        ErrorCode::InvalidOperation.into()
+    );
+    // The deadline rules hold when queued too, checked against the execution time
+    let mut active = self::migration();
+    active.claim_deadline = 5_000;
+    assert_eq!(
+        OperationKind::SetClaimDeadline { claim_deadline: 4_000 }.apply(&mut active, None, now).unwrap_err(),
+        ErrorCode::InvalidOperation.into()
+    );
+    assert_eq!(
+        OperationKind::SetClaimDeadline { claim_deadline: 9_000 }.apply(&mut active, None, 5_000).unwrap_err(),
+        ErrorCode::ClaimDeadlinePassed.into()
+    );
+
+    println!("✓ Queued operations applied");
+}
+
+#[test]
+fn test_negative_delay_rejected() {
+    assert_eq!(
+        OperationKind::SetTimelockDelay { delay: -1 }.validate().unwrap_err(),
+        ErrorCode::InvalidOperation.into()
+    );
+    OperationKind::SetTimelockDelay { delay: 0 }.validate().unwrap();
+    OperationKind::Finalize.validate().unwrap();
+}
+
+#[test]
+fn test_delay_capped_at_thirty_days() {
+    OperationKind::SetTimelockDelay { delay: MAX_TIMELOCK_DELAY }.validate().unwrap();
+    assert_eq!(
+        OperationKind::SetTimelockDelay { delay: MAX_TIMELOCK_DELAY + 1 }.validate().unwrap_err(),
+        ErrorCode::TimelockDelayTooLong.into()
+    );
+
+    let mut runtime = Runtime::new();
+    let state = migration();
+    let admin = runtime.add(TestAccount::signer(state.authority));
+    let migration = runtime.add_migration(state);
+    let set_delay = |delay| Instruction {
+        program_id: necro_migrate::ID,
+        accounts: necro_migrate::accounts::UpdateMigration { admin, migration }.to_account_metas(None),
+        data: necro_migrate::instruction::SetTimelockDelay { delay }.data(),
+    };
+
+    // An unbounded delay would freeze every queued operation, or overflow the ETA
+    let err = runtime.process(&set_delay(i64::MAX)).unwrap_err();
+    assert_eq!(err, runtime::program_error(ErrorCode::TimelockDelayTooLong));
+    runtime.process(&set_delay(MAX_TIMELOCK_DELAY)).unwrap();
+    assert_eq!(runtime.account::<Migration>(&migration).timelock_delay, 30 * 24 * 60 * 60);
+
+    println!("✓ Timelock delay capped");
+}
+
+#[test]
+fn test_invalid_snapshot_epoch_rejected_when_queued() {
+    let epoch = |supply_cap, claim_start, claim_end| OperationKind::AddSnapshotEpoch {
+        snapshot_root: [1u8; 32],
+        manifest_hash: [2u8; 32],
+        supply_cap,
+        claim_start,
+        claim_end,
+    };
+    epoch(10, 0, 1).validate().unwrap();
+    assert_eq!(epoch(0, 0, 1).validate().unwrap_err(), ErrorCode::InvalidAmount.into());
+    assert_eq!(epoch(10, 1, 1).validate().unwrap_err(), ErrorCode::InvalidOperation.into());
+}
+
+#[test]
+fn test_largest_operation_fits_account_space() {
+    let operation = queued(
+        OperationKind::AddSnapshotEpoch {
+            snapshot_root: [0xff; 32],
+            manifest_hash: [0xff; 32],
+            supply_cap: u64::MAX,
+            claim_start: i64::MAX,
+            claim_end: i64::MAX,
+        },
+        0,
+    );
+    let len = operation.try_to_vec().unwrap().len();
+    // Matches the field sizes documented on QueuedOperation
+    assert_eq!(len, 32 + 8 + 89 + 8 + 1 + 1);
+    assert!(len <= std::mem::size_of::<QueuedOperation>());
+}
+
+fn queued_operation_pda(migration: &Pubkey, id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"queued_op", migration.as_ref(), &id.to_le_bytes()], &necro_migrate::ID).0
+}
+
+fn queue_operation_ix(migration: Pubkey, admin: Pubkey, queued_operation: Pubkey, kind: OperationKind) -> Instruction {
+    Instruction {
+        program_id: necro_migrate::ID,
+        accounts: necro_migrate::accounts::QueueOperation {
+            admin,
+            migration,
+            queued_operation,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: necro_migrate::instruction::QueueOperation { kind }.data(),
+    }
+}
+
+/// `execute_operation`; `epoch` is the payer and address for an `AddSnapshotEpoch`
+fn execute_operation_ix(migration: Pubkey, queued_operation: Pubkey, epoch: Option<(Pubkey, Pubkey)>) -> Instruction {
+    Instruction {
+        program_id: necro_migrate::ID,
+        accounts: necro_migrate::accounts::ExecuteOperation {
+            migration,
+            queued_operation,
+            dao_liquidity: None,
+            payer: epoch.map(|(payer, _)| payer),
+            snapshot_epoch: epoch.map(|(_, snapshot_epoch)| snapshot_epoch),
+            system_program: epoch.map(|_| system_program::ID),
+        }
+        .to_account_metas(None),
+        data: necro_migrate::instruction::ExecuteOperation {}.data(),
+    }
+}
+
+#[test]
+fn test_operations_queue_under_the_next_id_and_wait_for_eta() {
+    let mut runtime = Runtime::new();
+    let state = migration();
+    let admin = runtime.add(TestAccount::signer(state.authority));
+    let migration = runtime.add_migration(state);
+    let guardian = Pubkey::new_unique();
+
+    // Direct guardian changes are closed while the timelock is on
+    let err = runtime
+        .process(&Instruction {
+            program_id: necro_migrate::ID,
+            accounts: necro_migrate::accounts::UpdateMigration { admin, migration }.to_account_metas(None),
+            data: necro_migrate::instruction::SetGuardian { guardian }.data(),
+        })
+        .unwrap_err();
+    assert_eq!(err, runtime::program_error(ErrorCode::TimelockRequired));
+
+    let first = queued_operation_pda(&migration, 0);
+    runtime.process(&queue_operation_ix(migration, admin, first, OperationKind::SetGuardian { guardian })).unwrap();
+
+    // Ids only move forward, so an address for a used id is refused
+    let err = runtime.process(&queue_operation_ix(migration, admin, first, OperationKind::Finalize)).unwrap_err();
+    assert_eq!(err, runtime::program_error(anchor_lang::error::ErrorCode::ConstraintSeeds));
+    let second = queued_operation_pda(&migration, 1);
+    runtime.process(&queue_operation_ix(migration, admin, second, OperationKind::Finalize)).unwrap();
+
+    let err = runtime.process(&execute_operation_ix(migration, first, None)).unwrap_err();
+    assert_eq!(err, runtime::program_error(ErrorCode::TimelockNotExpired));
+
+    runtime.warp(86_400);
+    runtime.process(&execute_operation_ix(migration, first, None)).unwrap();
+    assert_eq!(runtime.account::<Migration>(&migration).guardian, guardian);
+    assert_eq!(runtime.account::<QueuedOperation>(&first).status, OperationStatus::Executed);
+
+    let err = runtime.process(&execute_operation_ix(migration, first, None)).unwrap_err();
+    assert_eq!(err, runtime::program_error(ErrorCode::OperationNotQueued));
+    assert!(runtime.account::<Migration>(&migration).is_active);
+
+    println!("✓ Queued operations run once, in id order, after the ETA");
+}
+
+#[test]
+fn test_queued_snapshot_epoch_is_created_on_execute() {
+    let mut runtime = Runtime::new();
+    let state = migration();
+    let admin = runtime.add(TestAccount::signer(state.authority));
+    let migration = runtime.add_migration(state);
+    let payer = runtime.add(TestAccount::signer(Pubkey::new_unique()));
+    let now = runtime.now();
+
+    let kind = OperationKind::AddSnapshotEpoch {
+        snapshot_root: [3u8; 32],
+        manifest_hash: [4u8; 32],
+        supply_cap: 500,
+        claim_start: now,
+        claim_end: now + 1_000_000,
+    };
+    let operation = queued_operation_pda(&migration, 0);
+    runtime.process(&queue_operation_ix(migration, admin, operation, kind)).unwrap();
+    runtime.warp(86_400);
+
+    let (snapshot_epoch, bump) = Pubkey::find_program_address(
+        &[b"epoch", migration.as_ref(), &1u32.to_le_bytes()],
+        &necro_migrate::ID,
+    );
+    let err = runtime.process(&execute_operation_ix(migration, operation, None)).unwrap_err();
+    assert_eq!(err, runtime::program_error(ErrorCode::InvalidOperation));
+    runtime.process(&execute_operation_ix(migration, operation, Some((payer, snapshot_epoch)))).unwrap();
+
+    let epoch: SnapshotEpoch = runtime.account(&snapshot_epoch);
+    assert_eq!(epoch.migration, migration);
+    assert_eq!(epoch.epoch, 1);
+    assert_eq!(epoch.snapshot_root, [3u8; 32]);
+    assert_eq!(epoch.manifest_hash, [4u8; 32]);
+    assert_eq!(epoch.supply_cap, 500);
+    assert_eq!((epoch.claim_start, epoch.claim_end), (now, now + 1_000_000));
+    assert_eq!(epoch.bump, bump);
+    assert_eq!(runtime.account::<Migration>(&migration).epoch_count, 1);
+
+    println!("✓ Queued snapshot epoch created at execution");
+}
@@ -0,0 +1,80 @@
+#![cfg(test)]
+
+use necro_migrate::vesting::VestingSchedule;
+use necro_migrate::{merkle, ErrorCode};
+use solana_sdk::pubkey::Pubkey;
+
+const START: i64 = 1_700_000_000;
+const DAY: i64 = 86_400;
+
+fn schedule(cliff_days: i64, duration_days: i64) -> VestingSchedule {
+    VestingSchedule {
+        start_ts: START,
+        cliff_ts: START + cliff_days * DAY,
+        duration: duration_days * DAY,
+    }
+}
+
+#[test]
+fn test_linear_vesting() {
+    let schedule = schedule(0, 100);
+    let total = 1_000_000;
+
+    assert_eq!(schedule.vested_amount(total, START - 1), 0);
+    assert_eq!(schedule.vested_amount(total, START), 0);
+    assert_eq!(schedule.vested_amount(total, START + 25 * DAY), 250_000);
+    assert_eq!(schedule.vested_amount(total, START + 100 * DAY), total);
+    assert_eq!(schedule.vested_amount(total, i64::MAX), total);
+
+    println!("✓ Linear vesting unlocks proportionally");
+}
+
+#[test]
+fn test_cliff_releases_accrued_amount() {
+    let schedule = schedule(30, 120);
+    let total = 1_200;
+
+    assert_eq!(schedule.vested_amount(total, START + 30 * DAY - 1), 0);
+    // Everything accrued during the cliff unlocks at once
+    assert_eq!(schedule.vested_amount(total, START + 30 * DAY), 300);
+    assert_eq!(schedule.vested_amount(total, START + 60 * DAY), 600);
+
+    println!("✓ Cliff holds tokens then releases accrued share");
+}
+
+#[test]
+fn test_vesting_rounds_down_and_never_exceeds_total() {
+    let schedule = VestingSchedule { start_ts: 0, cliff_ts: 0, duration: 3 };
+
+    assert_eq!(schedule.vested_amount(10, 1), 3);
+    assert_eq!(schedule.vested_amount(10, 2), 6);
+    assert_eq!(schedule.vested_amount(10, 3), 10);
+
+    let large = VestingSchedule { start_ts: 0, cliff_ts: 0, duration: i64::MAX };
+    assert!(large.vested_amount(u64::MAX, i64::MAX - 1) < u64::MAX);
+}
+
+#[test]
+fn test_invalid_schedules_rejected() {
+    let invalid = [
+        VestingSchedule { start_ts: START, cliff_ts: START, duration: 0 },
+        VestingSchedule { start_ts: START, cliff_ts: START - 1, duration: DAY },
+        VestingSchedule { start_ts: START, cliff_ts: START + 2 * DAY, duration: DAY },
+    ];
+    for schedule in invalid {
+        assert_eq!(schedule.validate().unwrap_err(), ErrorCode::InvalidVestingSchedule.into());
+    }
+    schedule(30, 365).validate().unwrap();
+}
+
+#[test]
+fn test_vesting_leaf_commits_to_schedule() {
+    let wallet = Pubkey::new_unique();
+    let plain = merkle::leaf_hash(&wallet, 1_000, 0);
+    let vesting = merkle::vesting_leaf_hash(&wallet, 1_000, 0, &schedule(30, 365));
+    let shorter = merkle::vesting_leaf_hash(&wallet, 1_000, 0, &schedule(0, 1));
+
+    // A vesting allocation can't be claimed as an immediate one or with a shorter schedule
+    assert_ne!(plain, vesting);
+    assert_ne!(vesting, shorter);
+}
@@ -0,0 +1,233 @@
+#![cfg(test)]
+
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::system_instruction::SystemError;
+use anchor_lang::{system_program, InstructionData};
+use common::runtime::{self, Runtime};
+use common::TestAccount;
+use necro_migrate::wormhole::{ClaimPayload, PostedVaa, CLAIM_PAYLOAD_ID, CORE_BRIDGE_PROGRAM_ID};
+use necro_migrate::{DAOLiquidity, ErrorCode, Migration, VaaClaim};
+
+const SOURCE_CHAIN: u16 = 2; // Ethereum in Wormhole chain ids
+
+fn emitter() -> [u8; 32] {
+    // EVM address left-padded to 32 bytes
+    let mut address = [0u8; 32];
+    address[12..].copy_from_slice(&[0xab; 20]);
+    address
+}
+
+fn claim_payload(recipient: &Pubkey, amount: u64) -> Vec<u8> {
+    let mut payload = vec![CLAIM_PAYLOAD_ID];
+    payload.extend_from_slice(recipient.as_ref());
+    payload.extend_from_slice(&amount.to_be_bytes());
+    payload
+}
+
+/// Serialize a PostedVAA account the way the core bridge stores it
+fn posted_vaa_data(magic: &[u8; 3], sequence: u64, chain: u16, address: [u8; 32], payload: &[u8]) -> Vec<u8> {
+    let mut data = Vec::new();
+    data.extend_from_slice(magic);
+    data.push(1); // vaa_version
+    data.push(15); // consistency_level
+    data.extend_from_slice(&1_700_000_000u32.to_le_bytes()); // vaa_time
+    data.extend_from_slice(Pubkey::new_unique().as_ref()); // signature set
+    data.extend_from_slice(&1_700_000_100u32.to_le_bytes()); // submission_time
+    data.extend_from_slice(&42u32.to_le_bytes()); // nonce
+    data.extend_from_slice(&sequence.to_le_bytes());
+    data.extend_from_slice(&chain.to_le_bytes());
+    data.extend_from_slice(&address);
+    data.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    data.extend_from_slice(payload);
+    data
+}
+
+#[test]
+fn test_parse_fake_core_bridge_account() {
+    let recipient = Pubkey::new_unique();
+    let key = Pubkey::new_unique();
+    let mut lamports = 1_000_000;
+    let mut data = posted_vaa_data(b"vaa", 7, SOURCE_CHAIN, emitter(), &claim_payload(&recipient, 5_000));
+
+    let account = AccountInfo::new(
+        &key,
+        false,
+        false,
+        &mut lamports,
+        &mut data,
+        &CORE_BRIDGE_PROGRAM_ID,
+        false,
+        0,
+    );
+    assert_eq!(*account.owner, CORE_BRIDGE_PROGRAM_ID);
+
+    let vaa = PostedVaa::parse(&account.try_borrow_data().unwrap()).unwrap();
+    assert_eq!(vaa.sequence, 7);
+    assert_eq!(vaa.nonce, 42);
+    assert_eq!(vaa.emitter_chain, SOURCE_CHAIN);
+    assert_eq!(vaa.emitter_address, emitter());
+
+    let payload = ClaimPayload::parse(&vaa.payload).unwrap();
+    assert_eq!(payload.recipient, recipient);
+    assert_eq!(payload.amount, 5_000);
+
+    println!("✓ Fake core bridge VAA parsed");
+}
+
+#[test]
+fn test_emitter_must_match_migration_source() {
+    let data = posted_vaa_data(b"vaa", 3, SOURCE_CHAIN, emitter(), &claim_payload(&Pubkey::new_unique(), 1));
+    let vaa = PostedVaa::parse(&data).unwrap();
+
+    assert!(vaa.check_emitter(SOURCE_CHAIN, &emitter()).is_ok());
+    assert_eq!(vaa.check_emitter(4, &emitter()).unwrap_err(), ErrorCode::InvalidEmitter.into());
+    assert_eq!(vaa.check_emitter(SOURCE_CHAIN, &[0u8; 32]).unwrap_err(), ErrorCode::InvalidEmitter.into());
+}
+
+#[test]
+fn test_rejects_unverified_message() {
+    // Core bridge uses "msg"/"msu" for messages that have not been verified by guardians
+    let data = posted_vaa_data(b"msg", 1, SOURCE_CHAIN, emitter(), &claim_payload(&Pubkey::new_unique(), 1));
+    assert_eq!(PostedVaa::parse(&data).unwrap_err(), ErrorCode::InvalidVaa.into());
+}
+
+#[test]
+fn test_rejects_truncated_vaa() {
+    let data = posted_vaa_data(b"vaa", 1, SOURCE_CHAIN, emitter(), &claim_payload(&Pubkey::new_unique(), 1));
+    assert_eq!(PostedVaa::parse(&data[..data.len() - 1]).unwrap_err(), ErrorCode::InvalidVaa.into());
+    assert_eq!(PostedVaa::parse(&data[..50]).unwrap_err(), ErrorCode::InvalidVaa.into());
+}
+
+#[test]
+fn test_rejects_malformed_payload() {
+    let recipient = Pubkey::new_unique();
+
+    let mut wrong_id = claim_payload(&recipient, 10);
+    wrong_id[0] = 2;
+    assert_eq!(ClaimPayload::parse(&wrong_id).unwrap_err(), ErrorCode::InvalidVaaPayload.into());
+
+    let mut too_long = claim_payload(&recipient, 10);
+    too_long.push(0);
+    assert_eq!(ClaimPayload::parse(&too_long).unwrap_err(), ErrorCode::InvalidVaaPayload.into());
+}
+
+/// Run `claim_with_vaa` for `posted_vaa`, paid by `payer`
+fn claim_with_vaa(
+    runtime: &mut Runtime,
+    migration: Pubkey,
+    payer: Pubkey,
+    posted_vaa: Pubkey,
+    recipient_token_account: Pubkey,
+    dao_vault: Option<Pubkey>,
+    sequence: u64,
+) -> std::result::Result<(), ProgramError> {
+    let state: Migration = runtime.account(&migration);
+    let (vaa_claim, _) = Pubkey::find_program_address(
+        &[b"vaa_claim", migration.as_ref(), &sequence.to_le_bytes()],
+        &necro_migrate::ID,
+    );
+    let (dao_liquidity, _) =
+        Pubkey::find_program_address(&[b"dao_liquidity", migration.as_ref()], &necro_migrate::ID);
+
+    runtime.process(&Instruction {
+        program_id: necro_migrate::ID,
+        accounts: necro_migrate::accounts::ClaimWithVaa {
+            payer,
+            migration,
+            posted_vaa,
+            vaa_claim,
+            mint: state.mint,
+            token_vault: state.token_vault,
+            recipient_token_account,
+            dao_liquidity,
+            dao_vault,
+            migration_authority: runtime::authority_pda(&migration).0,
+            token_program: spl_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: necro_migrate::instruction::ClaimWithVaa { sequence }.data(),
+    })
+}
+
+#[test]
+fn test_replayed_vaa_is_rejected() {
+    let mut runtime = Runtime::new();
+    let migration = runtime.add_migration(Migration {
+        source_chain: SOURCE_CHAIN,
+        source_address: emitter(),
+        ..common::migration()
+    });
+    let mint = runtime.account::<Migration>(&migration).mint;
+
+    let recipient = Pubkey::new_unique();
+    let recipient_token_account = runtime.add_token_account(mint, recipient);
+    let payer = runtime.add(TestAccount::signer(Pubkey::new_unique()));
+    let posted_vaa = runtime.add(TestAccount::new(
+        Pubkey::new_unique(),
+        CORE_BRIDGE_PROGRAM_ID,
+        posted_vaa_data(b"vaa", 9, SOURCE_CHAIN, emitter(), &claim_payload(&recipient, 400)),
+    ));
+
+    claim_with_vaa(&mut runtime, migration, payer, posted_vaa, recipient_token_account, None, 9).unwrap();
+    assert_eq!(runtime.token_amount(&recipient_token_account), 400);
+
+    // The receipt for sequence 9 already exists, so its `init` fails
+    let replay = claim_with_vaa(&mut runtime, migration, payer, posted_vaa, recipient_token_account, None, 9);
+    assert_eq!(replay.unwrap_err(), ProgramError::Custom(SystemError::AccountAlreadyInUse as u32));
+    assert_eq!(runtime.token_amount(&recipient_token_account), 400);
+    assert_eq!(runtime.account::<Migration>(&migration).migrated_amount, 400);
+
+    println!("✓ Replayed VAA rejected");
+}
+
+#[test]
+fn test_vaa_claim_records_gross_and_net() {
+    let mut runtime = Runtime::new();
+    let migration = runtime.add_migration(Migration {
+        source_chain: SOURCE_CHAIN,
+        source_address: emitter(),
+        ..common::migration()
+    });
+    let mint = runtime.account::<Migration>(&migration).mint;
+    let (dao_liquidity, bump) =
+        Pubkey::find_program_address(&[b"dao_liquidity", migration.as_ref()], &necro_migrate::ID);
+    runtime.add(TestAccount::anchor(
+        dao_liquidity,
+        &DAOLiquidity {
+            migration,
+            dao_treasury: Pubkey::new_unique(),
+            lp_percentage: 10,
+            pool_initialized: false,
+            total_reserved: 0,
+            bump,
+            pending_treasury: Pubkey::default(),
+            amm_program: Pubkey::default(),
+            lp_mint: Pubkey::default(),
+        },
+    ));
+    let dao_vault = runtime.add_token_account(mint, dao_liquidity);
+
+    let recipient = Pubkey::new_unique();
+    let recipient_token_account = runtime.add_token_account(mint, recipient);
+    let payer = runtime.add(TestAccount::signer(Pubkey::new_unique()));
+    let posted_vaa = runtime.add(TestAccount::new(
+        Pubkey::new_unique(),
+        CORE_BRIDGE_PROGRAM_ID,
+        posted_vaa_data(b"vaa", 9, SOURCE_CHAIN, emitter(), &claim_payload(&recipient, 400)),
+    ));
+
+    claim_with_vaa(&mut runtime, migration, payer, posted_vaa, recipient_token_account, Some(dao_vault), 9).unwrap();
+    assert_eq!(runtime.token_amount(&recipient_token_account), 360);
+    assert_eq!(runtime.token_amount(&dao_vault), 40);
+
+    let (vaa_claim, _) =
+        Pubkey::find_program_address(&[b"vaa_claim", migration.as_ref(), &9u64.to_le_bytes()], &necro_migrate::ID);
+    let receipt: VaaClaim = runtime.account(&vaa_claim);
+    assert_eq!((receipt.amount, receipt.net_amount), (400, 360));
+
+    println!("✓ VAA claim records gross and net amounts");
+}
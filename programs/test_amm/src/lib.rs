@@ -0,0 +1,179 @@
+// Minimal constant-product AMM. It only supports creating a pool and adding
+// liquidity, which is all necro_migrate needs to seed the DAO pool under a
+// local validator. Not meant for mainnet.
+
+pub mod math;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+declare_id!("BA8UXkrThnsq18nuaTgYaVKP3Frsvw6ACWpi1QPbwhai");
+
+pub const LP_DECIMALS: u8 = 9;
+
+#[program]
+pub mod test_amm {
+    use super::*;
+
+    pub fn initialize_pool(ctx: Context<InitializePool>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+        pool.mint_a = ctx.accounts.mint_a.key();
+        pool.mint_b = ctx.accounts.mint_b.key();
+        pool.vault_a = ctx.accounts.vault_a.key();
+        pool.vault_b = ctx.accounts.vault_b.key();
+        pool.lp_mint = ctx.accounts.lp_mint.key();
+        pool.bump = ctx.bumps.pool;
+
+        msg!("Pool initialized");
+        Ok(())
+    }
+
+    /// Deposit both sides and mint LP tokens to `depositor_lp`
+    pub fn add_liquidity(ctx: Context<AddLiquidity>, amount_a: u64, amount_b: u64, min_lp: u64) -> Result<()> {
+        require!(amount_a > 0 && amount_b > 0, AmmError::InvalidAmount);
+
+        let minted = math::lp_for_deposit(
+            ctx.accounts.vault_a.amount,
+            ctx.accounts.vault_b.amount,
+            ctx.accounts.lp_mint.supply,
+            amount_a,
+            amount_b,
+        )
+        .ok_or(AmmError::InvalidAmount)?;
+        require!(minted >= min_lp, AmmError::SlippageExceeded);
+
+        let token_program = ctx.accounts.token_program.to_account_info();
+        for (from, mint, to, amount) in [
+            (&ctx.accounts.depositor_a, &ctx.accounts.mint_a, &ctx.accounts.vault_a, amount_a),
+            (&ctx.accounts.depositor_b, &ctx.accounts.mint_b, &ctx.accounts.vault_b, amount_b),
+        ] {
+            let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+                from: from.to_account_info(),
+                mint: mint.to_account_info(),
+                to: to.to_account_info(),
+                authority: ctx.accounts.depositor.to_account_info(),
+            };
+            anchor_spl::token_interface::transfer_checked(
+                CpiContext::new(token_program.clone(), cpi_accounts),
+                amount,
+                mint.decimals,
+            )?;
+        }
+
+        let pool = &ctx.accounts.pool;
+        let seeds = &[b"pool", pool.mint_a.as_ref(), pool.mint_b.as_ref(), &[pool.bump]];
+        let signer = &[&seeds[..]];
+        let cpi_accounts = anchor_spl::token_interface::MintTo {
+            mint: ctx.accounts.lp_mint.to_account_info(),
+            to: ctx.accounts.depositor_lp.to_account_info(),
+            authority: pool.to_account_info(),
+        };
+        anchor_spl::token_interface::mint_to(
+            CpiContext::new_with_signer(token_program, cpi_accounts, signer),
+            minted,
+        )?;
+
+        msg!("Added {} / {} liquidity for {} LP", amount_a, amount_b, minted);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    pub mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<Pool>(),
+        seeds = [b"pool", mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = mint_a,
+        token::authority = pool,
+        token::token_program = token_program,
+        seeds = [b"vault", pool.key().as_ref(), mint_a.key().as_ref()],
+        bump
+    )]
+    pub vault_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = mint_b,
+        token::authority = pool,
+        token::token_program = token_program,
+        seeds = [b"vault", pool.key().as_ref(), mint_b.key().as_ref()],
+        bump
+    )]
+    pub vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = LP_DECIMALS,
+        mint::authority = pool,
+        mint::token_program = token_program,
+        seeds = [b"lp_mint", pool.key().as_ref()],
+        bump
+    )]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddLiquidity<'info> {
+    pub depositor: Signer<'info>,
+
+    #[account(has_one = mint_a, has_one = mint_b, has_one = vault_a, has_one = vault_b, has_one = lp_mint)]
+    pub pool: Account<'info, Pool>,
+
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    pub mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub vault_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub depositor_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub depositor_b: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub depositor_lp: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[account]
+pub struct Pool {
+    pub mint_a: Pubkey,  // 32
+    pub mint_b: Pubkey,  // 32
+    pub vault_a: Pubkey, // 32
+    pub vault_b: Pubkey, // 32
+    pub lp_mint: Pubkey, // 32
+    pub bump: u8,        // 1
+}
+
+#[error_code]
+pub enum AmmError {
+    #[msg("Invalid amount")]
+    InvalidAmount,
+    #[msg("Fewer LP tokens than min_lp")]
+    SlippageExceeded,
+}
@@ -0,0 +1,41 @@
+// Constant-product pool math. LP shares are minted so that every holder's
+// claim on the reserves stays proportional: the first deposit gets
+// sqrt(a * b), later deposits get the smaller of their two reserve ratios.
+
+/// floor(sqrt(n)) for the first-deposit share count
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    let mut x = 1u128 << ((128 - n.leading_zeros()).div_ceil(2));
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// LP tokens minted for depositing `amount_a` and `amount_b`. Returns None on
+/// overflow or when the deposit would mint nothing.
+pub fn lp_for_deposit(
+    reserve_a: u64,
+    reserve_b: u64,
+    lp_supply: u64,
+    amount_a: u64,
+    amount_b: u64,
+) -> Option<u64> {
+    let minted = if lp_supply == 0 {
+        isqrt(amount_a as u128 * amount_b as u128)
+    } else {
+        if reserve_a == 0 || reserve_b == 0 {
+            return None;
+        }
+        let by_a = amount_a as u128 * lp_supply as u128 / reserve_a as u128;
+        let by_b = amount_b as u128 * lp_supply as u128 / reserve_b as u128;
+        by_a.min(by_b)
+    };
+    let minted = u64::try_from(minted).ok()?;
+    (minted > 0).then_some(minted)
+}
@@ -0,0 +1,31 @@
+#![cfg(test)]
+
+use test_amm::math::{isqrt, lp_for_deposit};
+
+#[test]
+fn test_first_deposit_mints_geometric_mean() {
+    assert_eq!(lp_for_deposit(0, 0, 0, 1_000_000, 100), Some(10_000));
+    assert_eq!(lp_for_deposit(0, 0, 0, u64::MAX, u64::MAX), Some(u64::MAX));
+    assert_eq!(lp_for_deposit(0, 0, 0, 0, 100), None);
+
+    println!("✓ First deposit");
+}
+
+#[test]
+fn test_later_deposits_are_proportional() {
+    // Pool at 1000:100 with 316 LP; a matching 10% deposit gets 10% more LP
+    assert_eq!(lp_for_deposit(1_000, 100, 316, 100, 10), Some(31));
+    // Over-supplying one side doesn't earn extra shares
+    assert_eq!(lp_for_deposit(1_000, 100, 316, 500, 10), Some(31));
+    // Empty reserves with outstanding LP can't be priced
+    assert_eq!(lp_for_deposit(0, 100, 316, 100, 10), None);
+}
+
+#[test]
+fn test_isqrt() {
+    for n in 0..10_000u128 {
+        let root = isqrt(n);
+        assert!(root * root <= n && (root + 1) * (root + 1) > n);
+    }
+    assert_eq!(isqrt(u64::MAX as u128 * u64::MAX as u128), u64::MAX as u128);
+}